@@ -0,0 +1,45 @@
+//! Encode-once broadcast benchmarks.
+
+
+#![allow(missing_docs)]
+
+
+use pipeworkmc_codec::{
+    encode::{
+        PacketEncode,
+        pre_encoded::PreEncoded
+    },
+    varint::VarInt
+};
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main
+};
+use std::hint::black_box;
+
+
+const PLAYERS : usize = 100;
+
+
+/// A packet with a few nested fields, similar to a chunk of entity metadata.
+fn packet() -> (String, Vec<VarInt<i32>>, Vec<u64>,) {
+    ("minecraft:entity_metadata".into(), (0..64).map(VarInt).collect(), (0..32).collect())
+}
+
+fn broadcast(c : &mut Criterion) {
+    let packet      = packet();
+    let pre_encoded = PreEncoded::new(&packet);
+    let mut group = c.benchmark_group("broadcast_100");
+    group.bench_function("encode_each", |b| b.iter(||
+        for _ in 0..PLAYERS { black_box(black_box(&packet).encode_to_vec()); }
+    ));
+    group.bench_function("pre_encoded", |b| b.iter(||
+        for _ in 0..PLAYERS { black_box(black_box(&pre_encoded).encode_to_vec()); }
+    ));
+    group.finish();
+}
+
+
+criterion_group!(benches, broadcast);
+criterion_main!(benches);
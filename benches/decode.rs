@@ -0,0 +1,42 @@
+//! Fixed-size byte array decode benchmarks.
+
+
+#![allow(missing_docs)]
+
+
+use pipeworkmc_codec::decode::DecodeIter;
+use criterion::{
+    Criterion,
+    Throughput,
+    criterion_group,
+    criterion_main
+};
+use std::hint::black_box;
+
+
+const LEN : usize = 1024;
+
+
+fn read_array(c : &mut Criterion) {
+    let bytes = (0..LEN).map(|i| i as u8).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("read_array_1024");
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.bench_function("read_arr", |b| b.iter(||
+        DecodeIter::from(black_box(&bytes).iter().copied()).read_arr::<LEN>().unwrap()
+    ));
+    group.bench_function("read_into_uninit", |b| b.iter(||
+        DecodeIter::from(black_box(&bytes).iter().copied()).read_into_uninit::<LEN>().unwrap()
+    ));
+    // Iterators which are not backed by a slice cannot use `next_chunk`'s copying fast path.
+    group.bench_function("read_arr_mapped", |b| b.iter(||
+        DecodeIter::from(black_box(&bytes).iter().map(|&b| b ^ 0x55)).read_arr::<LEN>().unwrap()
+    ));
+    group.bench_function("read_into_uninit_mapped", |b| b.iter(||
+        DecodeIter::from(black_box(&bytes).iter().map(|&b| b ^ 0x55)).read_into_uninit::<LEN>().unwrap()
+    ));
+    group.finish();
+}
+
+
+criterion_group!(benches, read_array);
+criterion_main!(benches);
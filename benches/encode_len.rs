@@ -0,0 +1,33 @@
+//! Byte slice encode length benchmarks.
+
+
+#![allow(missing_docs)]
+
+
+use pipeworkmc_codec::encode::PacketEncode;
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main
+};
+use std::hint::black_box;
+
+
+const LEN : usize = 1024 * 1024;
+
+
+fn encode_len(c : &mut Criterion) {
+    let vec   = vec![0u8; LEN];
+    let boxed = vec.clone().into_boxed_slice();
+    let wide  = vec![0u16; LEN];
+    let mut group = c.benchmark_group("encode_len_1mib");
+    group.bench_function("vec_u8", |b| b.iter(|| black_box(&vec).encode_len()));
+    group.bench_function("box_slice_u8", |b| b.iter(|| black_box(&boxed).encode_len()));
+    group.bench_function("slice_u8", |b| b.iter(|| black_box(&vec[..]).encode_len()));
+    group.bench_function("vec_u16", |b| b.iter(|| black_box(&wide).encode_len()));
+    group.finish();
+}
+
+
+criterion_group!(benches, encode_len);
+criterion_main!(benches);
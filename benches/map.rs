@@ -0,0 +1,41 @@
+//! Palette map encode benchmarks.
+
+
+#![allow(missing_docs)]
+
+
+use pipeworkmc_codec::{
+    encode::{
+        PacketEncode,
+        map::SortedMap
+    },
+    varint::VarInt
+};
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main
+};
+use std::{
+    collections::HashMap,
+    hint::black_box
+};
+
+
+const ENTRIES : i32 = 256;
+
+
+fn palette(c : &mut Criterion) {
+    let map    = (0..ENTRIES).map(|i| (VarInt(i), VarInt(i * 97))).collect::<HashMap<_, _>>();
+    let sorted = SortedMap(map.clone());
+    let fixed  = (0..ENTRIES).map(|i| (i, i * 97)).collect::<HashMap<_, _>>();
+    let mut group = c.benchmark_group("palette_256");
+    group.bench_function("hash_map_varint", |b| b.iter(|| black_box(&map).encode_to_vec()));
+    group.bench_function("sorted_map_varint", |b| b.iter(|| black_box(&sorted).encode_to_vec()));
+    group.bench_function("hash_map_i32", |b| b.iter(|| black_box(&fixed).encode_to_vec()));
+    group.finish();
+}
+
+
+criterion_group!(benches, palette);
+criterion_main!(benches);
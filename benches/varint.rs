@@ -0,0 +1,150 @@
+//! `VarInt` encode and decode benchmarks.
+
+
+#![allow(missing_docs)]
+
+
+use pipeworkmc_codec::{
+    decode::{
+        PacketDecode,
+        DecodeIter
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    },
+    varint::{ VarInt, VarIntArray }
+};
+use criterion::{
+    BenchmarkId,
+    Criterion,
+    Throughput,
+    criterion_group,
+    criterion_main
+};
+use std::hint::black_box;
+
+
+const COUNT : usize = 1024;
+
+
+/// Returns `COUNT` values spread over the given range, using a simple linear congruential generator.
+fn values(min : u32, max : u32) -> Vec<VarInt<i32>> {
+    let mut state = 0x2545F491u32;
+    (0..COUNT).map(|_| {
+        state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+        VarInt((min + (state % (max - min + 1))).cast_signed())
+    }).collect()
+}
+
+fn distributions() -> [(&'static str, Vec<VarInt<i32>>,); 3] { [
+    ("small",     values(0, 127),),
+    ("entity_id", values(1, 100_000),),
+    ("negative",  (0..COUNT).map(|i| VarInt(-1 - (i as i32))).collect(),)
+] }
+
+
+fn encode_len(c : &mut Criterion) {
+    let mut group = c.benchmark_group("varint_i32_encode_len");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, values,) in distributions() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &values, |b, values| b.iter(||
+            values.iter().map(|v| black_box(v).encode_len()).sum::<usize>()
+        ));
+    }
+    group.finish();
+}
+
+fn encode(c : &mut Criterion) {
+    let mut group = c.benchmark_group("varint_i32_encode");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, values,) in distributions() {
+        let len = values.iter().map(|v| v.encode_len()).sum();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &values, |b, values| b.iter(|| {
+            let mut buf = EncodeBuf::new(len);
+            for v in values {
+                // SAFETY: `buf` has exactly enough space for every value.
+                unsafe { black_box(v).encode(&mut buf); }
+            }
+            buf
+        }));
+    }
+    group.finish();
+}
+
+fn decode(c : &mut Criterion) {
+    let mut group = c.benchmark_group("varint_i32_decode");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, values,) in distributions() {
+        let bytes = values.iter().flat_map(|v| v.encode_to_vec()).collect::<Vec<_>>();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| b.iter(|| {
+            let mut iter = DecodeIter::from(black_box(bytes).iter().copied());
+            for _ in 0..COUNT {
+                black_box(VarInt::<i32>::decode(&mut iter).unwrap());
+            }
+        }));
+    }
+    group.finish();
+}
+
+fn worst_case_i64(c : &mut Criterion) {
+    let values = (0..COUNT).map(|i| VarInt(-1 - (i as i64))).collect::<Vec<_>>();
+    let bytes  = values.iter().flat_map(|v| v.encode_to_vec()).collect::<Vec<_>>();
+    let len    = bytes.len();
+    let mut group = c.benchmark_group("varint_i64_worst_case");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    group.bench_function("encode", |b| b.iter(|| {
+        let mut buf = EncodeBuf::new(len);
+        for v in &values {
+            // SAFETY: `buf` has exactly enough space for every value.
+            unsafe { black_box(v).encode(&mut buf); }
+        }
+        buf
+    }));
+    group.bench_function("decode", |b| b.iter(|| {
+        let mut iter = DecodeIter::from(black_box(&bytes).iter().copied());
+        for _ in 0..COUNT {
+            black_box(VarInt::<i64>::decode(&mut iter).unwrap());
+        }
+    }));
+    group.finish();
+}
+
+
+fn palette(c : &mut Criterion) {
+    let mut group = c.benchmark_group("varint_i32_palette");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, values,) in distributions() {
+        let array = VarIntArray(values.iter().map(|v| v.0).collect::<Vec<_>>());
+        let bytes = values.encode_to_vec();
+        group.bench_with_input(BenchmarkId::new("encode_vec", name), &values, |b, values|
+            b.iter(|| black_box(values).encode_to_vec())
+        );
+        group.bench_with_input(BenchmarkId::new("encode_array", name), &array, |b, array|
+            b.iter(|| black_box(array).encode_to_vec())
+        );
+        group.bench_with_input(BenchmarkId::new("decode_vec", name), &bytes, |b, bytes| b.iter(||
+            Vec::<VarInt<i32>>::decode(&mut DecodeIter::from(black_box(bytes).iter().copied())).unwrap()
+        ));
+        let mut scratch = VarIntArray::<i32>::default();
+        group.bench_with_input(BenchmarkId::new("decode_array", name), &bytes, |b, bytes| b.iter(|| {
+            scratch.decode_into(&mut DecodeIter::from(black_box(bytes).iter().copied())).unwrap();
+            black_box(scratch.len())
+        }));
+    }
+    group.finish();
+}
+
+
+fn nested(c : &mut Criterion) {
+    let values = (0..32).map(|_| values(0, 100_000)).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("varint_i32_nested");
+    group.throughput(Throughput::Elements((values.len() * COUNT) as u64));
+    group.bench_function("encode_len", |b| b.iter(|| black_box(&values).encode_len()));
+    group.bench_function("encode_to_vec", |b| b.iter(|| black_box(&values).encode_to_vec()));
+    group.finish();
+}
+
+
+criterion_group!(benches, encode_len, encode, decode, worst_case_i64, palette, nested);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+//! Decodes arbitrary bytes as a packet, re-encodes it, and checks that nothing was lost.
+//!
+//! Run with `cargo +nightly fuzz run roundtrip` from the repository root.
+
+
+#![no_main]
+
+
+use pipeworkmc_codec::{
+    decode::{
+        PacketDecode,
+        PrefixedPacketDecode,
+        DecodeIter,
+        IncompleteDecodeError
+    },
+    encode::{
+        PacketEncode,
+        PrefixedPacketEncode,
+        EncodeBuf
+    },
+    meta::{
+        PacketMeta,
+        PacketState,
+        PacketBound
+    },
+    varint::VarInt
+};
+use libfuzzer_sys::fuzz_target;
+
+
+/// A packet which covers the common field types.
+#[derive(PartialEq, Debug)]
+struct RoundtripPacket {
+    entity_id : VarInt<i32>,
+    name      : String,
+    data      : Vec<u8>,
+    flags     : u16
+}
+
+impl PacketMeta for RoundtripPacket {
+    const STATE         : PacketState = PacketState::Play;
+    const BOUND         : PacketBound = PacketBound::S2C;
+    const PREFIX        : u32         = 0x2A;
+    const VARINT_PREFIX : bool        = true;
+}
+
+/// Any decode error. The fuzz target only cares whether decoding succeeded.
+#[derive(Debug)]
+struct RoundtripError;
+
+impl From<IncompleteDecodeError> for RoundtripError {
+    #[inline(always)]
+    fn from(_ : IncompleteDecodeError) -> Self { Self }
+}
+
+impl PacketDecode for RoundtripPacket {
+    type Error = RoundtripError;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self {
+        entity_id : VarInt::decode(iter).map_err(|_| RoundtripError)?,
+        name      : String::decode(iter).map_err(|_| RoundtripError)?,
+        data      : Vec::decode(iter).map_err(|_| RoundtripError)?,
+        flags     : u16::decode(iter).map_err(|_| RoundtripError)?
+    }) }
+}
+
+unsafe impl PacketEncode for RoundtripPacket {
+
+    fn encode_len(&self) -> usize {
+        self.entity_id.encode_len()
+        + self.name.encode_len()
+        + self.data.encode_len()
+        + self.flags.encode_len()
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.entity_id.encode(buf);
+        self.name.encode(buf);
+        self.data.encode(buf);
+        self.flags.encode(buf);
+    } }
+
+}
+
+
+fuzz_target!(|data : &[u8]| {
+    let mut iter = DecodeIter::from(data.iter().copied());
+    let Ok(packet) = RoundtripPacket::decode_prefixed(&mut iter) else { return; };
+    let consumed = &data[..iter.consumed()];
+
+    let encoded = packet.encode_prefixed_to_vec();
+    assert_eq!(encoded.len(), packet.encode_prefixed_len());
+    // Non-canonical `VarInt`s are always longer than their canonical encoding,
+    //  so any input which re-encodes to the same length must re-encode to the same bytes.
+    if (encoded.len() == consumed.len()) {
+        assert_eq!(encoded, consumed);
+    } else {
+        assert!(encoded.len() < consumed.len());
+    }
+
+    let redecoded = RoundtripPacket::decode_prefixed(&mut DecodeIter::from(encoded.iter().copied())).unwrap();
+    assert_eq!(redecoded, packet);
+});
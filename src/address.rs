@@ -0,0 +1,109 @@
+//! The handshake server address type.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError,
+    string::StringDecodeError
+};
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    net::SocketAddr
+};
+use alloc::string::{ String, ToString };
+
+
+/// The address that a client used to connect, as sent in the handshake.
+///
+/// Encoded as a `String` address, followed by a `u16` port.
+/// The address is usually a hostname, but may also be an IP address.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HandshakeAddress {
+    /// The hostname or IP address.
+    pub address : String,
+    /// The port.
+    pub port    : u16
+}
+
+impl HandshakeAddress {
+
+    /// The maximum number of characters that the protocol allows in the address.
+    pub const MAX_ADDRESS_CHARS : usize = 255;
+
+    /// Creates a new [`HandshakeAddress`].
+    #[inline]
+    pub fn new(address : &str, port : u16) -> Self {
+        Self { address : address.into(), port }
+    }
+
+}
+
+impl From<SocketAddr> for HandshakeAddress {
+    #[inline]
+    fn from(value : SocketAddr) -> Self {
+        Self { address : value.ip().to_string(), port : value.port() }
+    }
+}
+
+/// Formats as `address:port`.
+///
+/// IPv6 addresses are wrapped in brackets, such as `[::1]:25565`, so that the port can be told apart from the address.
+impl Display for HandshakeAddress {
+    #[inline]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        if (self.address.contains(':') && ! self.address.starts_with('[')) {
+            write!(f, "[{}]:{}", self.address, self.port)
+        } else {
+            write!(f, "{}:{}", self.address, self.port)
+        }
+    }
+}
+
+
+impl PacketDecode for HandshakeAddress {
+    type Error = HandshakeAddressDecodeError;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self {
+        address : iter.read_string(Self::MAX_ADDRESS_CHARS).map_err(HandshakeAddressDecodeError::Address)?,
+        port    : u16::decode(iter).map_err(HandshakeAddressDecodeError::Port)?
+    }) }
+}
+
+unsafe impl PacketEncode for HandshakeAddress {
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.address.encode_len()
+        + self.port.encode_len()
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.address.encode(buf);
+        self.port.encode(buf);
+    } }
+
+}
+
+
+/// Returned by packet decoders when a [`HandshakeAddress`] was not decoded successfully.
+#[derive(Debug)]
+pub enum HandshakeAddressDecodeError {
+    /// The address failed to decode.
+    Address(StringDecodeError),
+    /// The port failed to decode.
+    Port(IncompleteDecodeError)
+}
+impl Display for HandshakeAddressDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Address(err) => write!(f, "address {err}"),
+        Self::Port(err)    => write!(f, "port {err}")
+    } }
+}
@@ -0,0 +1,114 @@
+//! The rotation angle type.
+
+
+use crate::{
+    decode::{
+        PacketDecode,
+        DecodeIter,
+        IncompleteDecodeError
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    }
+};
+use core::{
+    f32::math,
+    ops::{ Add, AddAssign, Sub, SubAssign }
+};
+
+
+/// A rotation angle, in steps of 1/256 of a full turn.
+///
+/// All arithmetic wraps around modulo a full turn.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Angle(pub u8);
+
+impl Angle {
+
+    /// The number of steps in a full turn.
+    pub const STEPS : u16 = 256;
+
+    /// Creates a new [`Angle`] from an angle in degrees, rounding to the nearest step.
+    #[inline]
+    pub fn from_degrees(degrees : f32) -> Self {
+        Self(math::round((math::rem_euclid(degrees, 360.0) / 360.0) * (Self::STEPS as f32)) as u16 as u8)
+    }
+
+    /// Returns this angle in degrees, in the range `0.0..360.0`.
+    #[inline]
+    pub fn to_degrees(self) -> f32 {
+        ((self.0 as f32) / (Self::STEPS as f32)) * 360.0
+    }
+
+    /// Adds `other` to this angle, wrapping around at a full turn.
+    #[inline(always)]
+    pub const fn wrapping_add(self, other : Self) -> Self {
+        Self(self.0.wrapping_add(other.0))
+    }
+
+    /// Subtracts `other` from this angle, wrapping around at a full turn.
+    #[inline(always)]
+    pub const fn wrapping_sub(self, other : Self) -> Self {
+        Self(self.0.wrapping_sub(other.0))
+    }
+
+    /// Returns the angle which must be added to this angle to reach `other`.
+    ///
+    /// The returned angle is the shortest rotation, as given by [`Angle::as_signed_steps`].
+    #[inline(always)]
+    pub const fn delta_to(self, other : Self) -> Self {
+        other.wrapping_sub(self)
+    }
+
+    /// Returns this angle as a signed number of steps, in the range `-128..=127`.
+    ///
+    /// Negative values rotate in the opposite direction.
+    #[inline(always)]
+    pub const fn as_signed_steps(self) -> i8 {
+        self.0.cast_signed()
+    }
+
+}
+
+impl Add for Angle {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, other : Self) -> Self::Output { self.wrapping_add(other) }
+}
+impl AddAssign for Angle {
+    #[inline(always)]
+    fn add_assign(&mut self, other : Self) { *self = self.wrapping_add(other); }
+}
+impl Sub for Angle {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, other : Self) -> Self::Output { self.wrapping_sub(other) }
+}
+impl SubAssign for Angle {
+    #[inline(always)]
+    fn sub_assign(&mut self, other : Self) { *self = self.wrapping_sub(other); }
+}
+
+
+impl PacketDecode for Angle {
+    type Error = IncompleteDecodeError;
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(iter.read()?)) }
+}
+
+unsafe impl PacketEncode for Angle {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { size_of::<u8>() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write(self.0)
+    } }
+
+}
@@ -1,14 +1,17 @@
-//! `[T; N]` decoder.
+//! `[T; N]` and `UnprefixedArray<u8, N>` decoders.
 
 
 use crate::decode::{
     PacketDecode,
-    DecodeIter
-};
-use crate::varint::{
-    VarInt,
-    VarIntDecodeError
+    DecodeIter,
+    DepthLimitExceededError,
+    IncompleteDecodeError,
+    LengthDecodeError,
+    MAX_PREFIXED_LEN,
+    decode_prefixed_len
 };
+use crate::encode::slice::UnprefixedArray;
+use crate::varint::VarIntDecodeError;
 use core::mem::MaybeUninit;
 
 
@@ -22,26 +25,70 @@ where
     where
         I : ExactSizeIterator<Item = u8>
     {
-        let length = *VarInt::<u32>::decode(iter).map_err(ArrayDecodeError::Length)? as usize;
-        if (length != N) {
-            return Err(ArrayDecodeError::BadLength { len : length, expected : N });
-        }
-        let mut arr = [const { MaybeUninit::uninit() }; N];
-        for i in 0..N {
-            match (T::decode(iter).map_err(|err| ArrayDecodeError::Item { index : i, err })) {
-                // SAFETY: `i` is guaranteed to be less than `arr.len()`.
-                Ok(item) => unsafe { arr.get_unchecked_mut(i).write(item); },
-                Err(err) => {
-                    for j in 0..i {
-                        // SAFETY: Up to, but not including, `i` items in `arr` are guaranteed to have been written.
-                        unsafe { arr.get_unchecked_mut(j).assume_init_drop(); }
+        iter.nested(|iter| {
+            let length = decode_prefixed_len(iter, MAX_PREFIXED_LEN).map_err(ArrayDecodeError::Length)?;
+            if (length != N) {
+                return Err(ArrayDecodeError::BadLength { len : length, expected : N });
+            }
+            let mut arr = [const { MaybeUninit::uninit() }; N];
+            for i in 0..N {
+                match (T::decode(iter).map_err(|err| ArrayDecodeError::Item { index : i, err })) {
+                    // SAFETY: `i` is guaranteed to be less than `arr.len()`.
+                    Ok(item) => unsafe { arr.get_unchecked_mut(i).write(item); },
+                    Err(err) => {
+                        for j in 0..i {
+                            // SAFETY: Up to, but not including, `i` items in `arr` are guaranteed to have been written.
+                            unsafe { arr.get_unchecked_mut(j).assume_init_drop(); }
+                        }
+                        return Err(err);
                     }
-                    return Err(err);
                 }
             }
+            // SAFETY: All bytes in `arr` were written.
+            Ok(unsafe { MaybeUninit::array_assume_init(arr) })
+        })
+    }
+}
+
+
+/// Skips the `len` items of an array after its length has been decoded, such as after [`ArrayDecodeError::BadLength`].
+///
+/// Each item is decoded as a `T` and then dropped, as the encoded length of an item is not known in advance.
+/// If an item fails to decode, the iterator is left in an indeterminate position and the rest of the packet should be discarded.
+///
+/// A `len` which could not fit in the remaining bytes is rejected with [`LengthDecodeError::TooLong`] before any items are skipped.
+/// Items which can be decoded from no bytes are counted as taking at least one byte, so that a huge `len` can not loop for a long time.
+pub fn skip_remaining_array<T, I>(iter : &mut DecodeIter<I>, len : usize) -> Result<(), ArrayDecodeError<T::Error>>
+where
+    T : PacketDecode,
+    I : ExactSizeIterator<Item = u8>
+{
+    let min_len = T::MIN_ENCODED_LEN.max(1);
+    if (len.saturating_mul(min_len) > iter.remaining()) {
+        return Err(ArrayDecodeError::Length(LengthDecodeError::TooLong { len, max : iter.remaining() / min_len }));
+    }
+    iter.nested(|iter| {
+        for i in 0..len {
+            T::decode(iter).map_err(|err| ArrayDecodeError::Item { index : i, err })?;
         }
-        // SAFETY: All bytes in `arr` were written.
-        Ok(unsafe { MaybeUninit::array_assume_init(arr) })
+        Ok(())
+    })
+}
+
+
+/// Decodes exactly `N` bytes, without a [`VarInt`](crate::varint::VarInt) length, in a single read.
+impl<const N : usize> PacketDecode for UnprefixedArray<u8, N> {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = N;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let mut arr = [0u8; N];
+        iter.read_buf(&mut arr)?;
+        Ok(Self(arr))
     }
 }
 
@@ -50,8 +97,13 @@ where
 #[derive(Debug)]
 pub enum ArrayDecodeError<E> {
     /// The length of the array failed to decode.
-    Length(VarIntDecodeError),
+    Length(LengthDecodeError),
+    /// Collections were nested too deeply.
+    Depth(DepthLimitExceededError),
     /// The length of the decoded array does not match the expected length.
+    ///
+    /// The iterator is left just after the length, before the first item.
+    /// [`skip_remaining_array`] can be used to skip the items, or the rest of the packet can be discarded.
     BadLength {
         /// The length of the decoded array.
         len      : usize,
@@ -66,3 +118,15 @@ pub enum ArrayDecodeError<E> {
         err   : E
     }
 }
+impl<E> From<DepthLimitExceededError> for ArrayDecodeError<E> {
+    #[inline(always)]
+    fn from(err : DepthLimitExceededError) -> Self { Self::Depth(err) }
+}
+impl<E> From<LengthDecodeError> for ArrayDecodeError<E> {
+    #[inline(always)]
+    fn from(err : LengthDecodeError) -> Self { Self::Length(err) }
+}
+impl<E> From<VarIntDecodeError> for ArrayDecodeError<E> {
+    #[inline(always)]
+    fn from(err : VarIntDecodeError) -> Self { Self::Length(LengthDecodeError::VarInt(err)) }
+}
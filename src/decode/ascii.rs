@@ -0,0 +1,160 @@
+//! `FixedAscii<N>` and `AsciiChar` decoders.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
+};
+use alloc::string::String;
+
+
+/// An ASCII string of exactly `N` bytes, padded with trailing NULs.
+///
+/// Trailing NULs are removed when decoding, and added when encoding.
+/// When encoding, strings longer than `N` bytes are truncated.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct FixedAscii<const N : usize>(pub String);
+
+impl<const N : usize> FixedAscii<N> {
+
+    /// Creates a new [`FixedAscii`], returning `None` if `string` is not ASCII or is longer than `N` bytes.
+    #[inline]
+    pub fn new(string : String) -> Option<Self> {
+        (string.is_ascii() && string.len() <= N).then_some(Self(string))
+    }
+
+}
+
+impl<const N : usize> Deref for FixedAscii<N> {
+    type Target = str;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<const N : usize> PacketDecode for FixedAscii<N> {
+    type Error = FixedAsciiError;
+    const MIN_ENCODED_LEN : usize = N;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let mut bytes = iter.read_vec(N)?;
+        if let Some(index) = bytes.iter().position(|b| ! b.is_ascii()) {
+            return Err(FixedAsciiError::NonAscii { index, byte : bytes[index] });
+        }
+        let len = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        bytes.truncate(len);
+        // SAFETY: All bytes were checked to be ASCII.
+        Ok(Self(unsafe { String::from_utf8_unchecked(bytes) }))
+    }
+}
+
+
+/// Returned by packet decoders when a `FixedAscii<N>` was not decoded successfully.
+#[derive(Debug)]
+pub enum FixedAsciiError {
+    /// There were not enough bytes.
+    Incomplete(IncompleteDecodeError),
+    /// A byte in the string was not ASCII.
+    NonAscii {
+        /// The index of the byte.
+        index : usize,
+        /// The byte.
+        byte  : u8
+    }
+}
+impl From<IncompleteDecodeError> for FixedAsciiError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Incomplete(err) }
+}
+impl Display for FixedAsciiError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Incomplete(err)          => err.fmt(f),
+        Self::NonAscii { index, byte } => write!(f, "non-ascii byte {byte:#04x} at {index}")
+    } }
+}
+
+
+/// A single ASCII character, encoded as one byte.
+///
+/// This is used by protocols which select between options with a character such as `'0'` or `'1'`.
+/// For a character encoded as a [`VarInt`](crate::varint::VarInt) codepoint, use `char` instead.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct AsciiChar(u8);
+
+impl AsciiChar {
+
+    /// Creates a new [`AsciiChar`], returning `None` if `ch` is not ASCII.
+    #[inline]
+    pub const fn new(ch : char) -> Option<Self> {
+        if (ch.is_ascii()) { Some(Self(ch as u8)) } else { None }
+    }
+
+    /// Creates a new [`AsciiChar`] from a byte, returning `None` if `byte` is not ASCII.
+    #[inline]
+    pub const fn from_byte(byte : u8) -> Option<Self> {
+        if (byte.is_ascii()) { Some(Self(byte)) } else { None }
+    }
+
+    /// Returns the character.
+    #[inline(always)]
+    pub const fn to_char(self) -> char { self.0 as char }
+
+    /// Returns the character as a byte.
+    #[inline(always)]
+    pub const fn to_byte(self) -> u8 { self.0 }
+
+}
+
+impl From<AsciiChar> for char {
+    #[inline(always)]
+    fn from(ch : AsciiChar) -> Self { ch.to_char() }
+}
+
+impl From<AsciiChar> for u8 {
+    #[inline(always)]
+    fn from(ch : AsciiChar) -> Self { ch.to_byte() }
+}
+
+impl Display for AsciiChar {
+    #[inline]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { self.to_char().fmt(f) }
+}
+
+impl PacketDecode for AsciiChar {
+    type Error = AsciiCharError;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let byte = iter.read()?;
+        Self::from_byte(byte).ok_or(AsciiCharError::NonAscii(byte))
+    }
+}
+
+
+/// Returned by packet decoders when an `AsciiChar` was not decoded successfully.
+#[derive(Debug)]
+pub enum AsciiCharError {
+    /// There were not enough bytes.
+    Incomplete(IncompleteDecodeError),
+    /// The byte was not ASCII.
+    NonAscii(u8)
+}
+impl From<IncompleteDecodeError> for AsciiCharError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Incomplete(err) }
+}
+impl Display for AsciiCharError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Incomplete(err) => err.fmt(f),
+        Self::NonAscii(byte)  => write!(f, "non-ascii byte {byte:#04x}")
+    } }
+}
@@ -0,0 +1,69 @@
+//! An exact size iterator over two chained byte iterators.
+
+
+use crate::decode::DecodeIter;
+
+
+/// An iterator over the bytes of `A`, followed by the bytes of `B`.
+///
+/// Unlike [`core::iter::Chain`], this implements [`ExactSizeIterator`].
+#[derive(Clone, Debug)]
+pub struct ExactChain<A, B>
+where
+    A : ExactSizeIterator<Item = u8>,
+    B : ExactSizeIterator<Item = u8>
+{
+    a : A,
+    b : B
+}
+
+impl<A, B> ExactChain<A, B>
+where
+    A : ExactSizeIterator<Item = u8>,
+    B : ExactSizeIterator<Item = u8>
+{
+
+    /// Creates a new [`ExactChain`] over the bytes of `a`, followed by the bytes of `b`.
+    #[inline(always)]
+    pub fn new(a : A, b : B) -> Self { Self { a, b } }
+
+}
+
+impl<A, B> Iterator for ExactChain<A, B>
+where
+    A : ExactSizeIterator<Item = u8>,
+    B : ExactSizeIterator<Item = u8>
+{
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if (self.a.len() > 0) { self.a.next() } else { self.b.next() }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len),)
+    }
+}
+impl<A, B> ExactSizeIterator for ExactChain<A, B>
+where
+    A : ExactSizeIterator<Item = u8>,
+    B : ExactSizeIterator<Item = u8>
+{ }
+
+
+impl<A, B> DecodeIter<ExactChain<A, B>>
+where
+    A : ExactSizeIterator<Item = u8>,
+    B : ExactSizeIterator<Item = u8>
+{
+
+    /// Creates a new [`DecodeIter`] over the bytes of `a`, followed by the bytes of `b`.
+    ///
+    /// This can be used to decode a packet which is split across two buffers, without copying them into one.
+    #[inline(always)]
+    pub fn from_chained(a : A, b : B) -> Self {
+        Self::from(ExactChain::new(a, b))
+    }
+
+}
@@ -0,0 +1,48 @@
+//! `Checkpoint<I>`, for speculative decoding.
+
+
+use crate::decode::DecodeIter;
+
+
+/// A saved position of a [`DecodeIter`], which can be returned to with [`DecodeIter::restore`].
+#[derive(Clone, Debug)]
+pub struct Checkpoint<I> {
+    iter  : I,
+    head  : usize,
+    depth : usize
+}
+
+impl<I> Checkpoint<I> {
+
+    /// Returns the number of bytes which had been consumed when this checkpoint was taken.
+    #[inline(always)]
+    pub fn consumed(&self) -> usize { self.head }
+
+}
+
+impl<I> DecodeIter<I>
+where
+    I : ExactSizeIterator<Item = u8> + Clone
+{
+
+    /// Saves the current position, so that it can be returned to with [`restore`](Self::restore).
+    ///
+    /// This clones the inner iterator, so it is only cheap for seekable sources, such as a [`slice::Iter`](core::slice::Iter).
+    /// Iterators which own their bytes, such as [`vec::IntoIter`](alloc::vec::IntoIter), clone all of the remaining bytes.
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint<I> {
+        Checkpoint { iter : self.iter.clone(), head : self.head, depth : self.depth }
+    }
+
+    /// Returns to a position saved by [`checkpoint`](Self::checkpoint), rewinding [`consumed`](Self::consumed).
+    ///
+    /// `checkpoint` should have been taken from this [`DecodeIter`].
+    /// A checkpoint from a different one replaces the remaining bytes with that one's.
+    #[inline(always)]
+    pub fn restore(&mut self, checkpoint : Checkpoint<I>) {
+        self.iter  = checkpoint.iter;
+        self.head  = checkpoint.head;
+        self.depth = checkpoint.depth;
+    }
+
+}
@@ -1,23 +1,80 @@
+//! `chrono` date, time, and duration decoders.
+
+
 use crate::decode::{
     PacketDecode,
-    DecodeBuf,
+    DecodeIter,
     IncompleteDecodeError
 };
-use chrono::{ DateTime, Utc };
+use core::fmt::{ self, Display, Formatter };
+use core::error::Error;
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    TimeDelta,
+    Utc
+};
 
 
+/// Decoded as an `i64` number of seconds since the Unix epoch, followed by a `u32` number of nanoseconds.
 impl PacketDecode for DateTime<Utc> {
     type Error = DateTimeDecodeError;
-    fn decode(iter : &mut DecodeBuf<'_>)
-        -> Result<Self, Self::Error>
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
     { Self::from_timestamp(
         <_>::decode(iter).map_err(DateTimeDecodeError::Secs)?,
         <_>::decode(iter).map_err(DateTimeDecodeError::Nanos)?
     ).ok_or(DateTimeDecodeError::Invalid) }
 }
 
+/// Decoded in the same layout as [`DateTime<Utc>`].
+impl PacketDecode for NaiveDateTime {
+    type Error = DateTimeDecodeError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { DateTime::<Utc>::decode(iter).map(|datetime| datetime.naive_utc()) }
+}
+
+/// Decoded as an `i64` number of milliseconds.
+impl PacketDecode for TimeDelta {
+    type Error = DateTimeDecodeError;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Self::try_milliseconds(
+        <_>::decode(iter).map_err(DateTimeDecodeError::Millis)?
+    ).ok_or(DateTimeDecodeError::Invalid) }
+}
+
+
+/// Returned by packet decoders when a `DateTime<Utc>`, `NaiveDateTime`, or `TimeDelta` was not decoded successfully.
+#[derive(Debug)]
 pub enum DateTimeDecodeError {
+    /// The seconds failed to decode.
     Secs(IncompleteDecodeError),
+    /// The nanoseconds failed to decode.
     Nanos(IncompleteDecodeError),
+    /// The milliseconds failed to decode.
+    Millis(IncompleteDecodeError),
+    /// The decoded value was out of range.
     Invalid
 }
+impl From<IncompleteDecodeError> for DateTimeDecodeError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Secs(err) }
+}
+impl Display for DateTimeDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Secs(err)   => write!(f, "secs {err}"),
+        Self::Nanos(err)  => write!(f, "nanos {err}"),
+        Self::Millis(err) => write!(f, "millis {err}"),
+        Self::Invalid     => write!(f, "out of range")
+    } }
+}
+impl Error for DateTimeDecodeError { }
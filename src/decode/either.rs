@@ -0,0 +1,67 @@
+//! `TaggedEither<L, R>` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::fmt::{ self, Display, Formatter };
+
+
+/// One of two values, prefixed with a `bool` tag.
+///
+/// A tag of `true` is followed by a `L`, and a tag of `false` is followed by a `R`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TaggedEither<L, R> {
+    /// The value tagged with `true`.
+    Left(L),
+    /// The value tagged with `false`.
+    Right(R)
+}
+
+impl<L, R> PacketDecode for TaggedEither<L, R>
+where
+    L : PacketDecode,
+    R : PacketDecode
+{
+    type Error = TaggedEitherDecodeError<L::Error, R::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        if (bool::decode(iter)?) {
+            Ok(Self::Left(L::decode(iter).map_err(TaggedEitherDecodeError::Left)?))
+        } else {
+            Ok(Self::Right(R::decode(iter).map_err(TaggedEitherDecodeError::Right)?))
+        }
+    }
+}
+
+
+/// Returned by packet decoders when a `TaggedEither<L, R>` was not decoded successfully.
+#[derive(Debug)]
+pub enum TaggedEitherDecodeError<L, R> {
+    /// The tag failed to decode.
+    Tag(IncompleteDecodeError),
+    /// The value tagged with `true` failed to decode.
+    Left(L),
+    /// The value tagged with `false` failed to decode.
+    Right(R)
+}
+impl<L, R> From<IncompleteDecodeError> for TaggedEitherDecodeError<L, R> {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Tag(err) }
+}
+impl<L, R> Display for TaggedEitherDecodeError<L, R>
+where
+    L : Display,
+    R : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Tag(err)   => write!(f, "tag {err}"),
+        Self::Left(err)  => write!(f, "left {err}"),
+        Self::Right(err) => write!(f, "right {err}")
+    } }
+}
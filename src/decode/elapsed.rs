@@ -0,0 +1,67 @@
+//! `ElapsedMillis` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter
+};
+use crate::varint::{
+    VarInt,
+    VarIntDecodeError
+};
+use core::{
+    ops::Deref,
+    time::Duration
+};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+
+/// A [`Duration`] which is sent as a [`VarInt<u64>`] of whole milliseconds, such as a ping round-trip time.
+///
+/// When encoding, anything smaller than a millisecond is truncated, and durations longer than [`u64::MAX`] milliseconds are saturated.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct ElapsedMillis(pub Duration);
+
+impl ElapsedMillis {
+
+    /// Returns the time elapsed from `start` to `end`, or zero if `end` is before `start`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_instants(start : Instant, end : Instant) -> Self {
+        Self(end.saturating_duration_since(start))
+    }
+
+    /// Returns the number of whole milliseconds, as it will be encoded.
+    #[inline]
+    pub fn to_millis(self) -> u64 {
+        u64::try_from(self.0.as_millis()).unwrap_or(u64::MAX)
+    }
+
+}
+
+impl From<Duration> for ElapsedMillis {
+    #[inline(always)]
+    fn from(duration : Duration) -> Self { Self(duration) }
+}
+
+impl From<ElapsedMillis> for Duration {
+    #[inline(always)]
+    fn from(elapsed : ElapsedMillis) -> Self { elapsed.0 }
+}
+
+impl Deref for ElapsedMillis {
+    type Target = Duration;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for ElapsedMillis {
+    type Error = VarIntDecodeError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(Duration::from_millis(*VarInt::<u64>::decode(iter)?))) }
+}
@@ -0,0 +1,80 @@
+//! `ExactLen<I>` iterator adapter.
+
+
+use crate::decode::DecodeIter;
+
+
+/// An iterator of bytes with a known length, which can be used by a [`DecodeIter`].
+///
+/// This lets iterators which do not implement [`ExactSizeIterator`], such as a [`Peekable`](core::iter::Peekable) over a network buffer
+///  or a [`Chain`](core::iter::Chain) of buffers, be decoded without collecting them first.
+/// [`Peekable`](core::iter::Peekable) already implements [`ExactSizeIterator`] when the iterator it wraps does.
+///
+/// No more than `len` bytes are yielded.
+/// If the inner iterator ends early, decoding returns an [`IncompleteDecodeError`](crate::decode::IncompleteDecodeError) when it runs out,
+///  and the remaining length becomes 0.
+#[derive(Clone, Debug)]
+pub struct ExactLen<I>
+where
+    I : Iterator<Item = u8>
+{
+    iter      : I,
+    remaining : usize
+}
+
+impl<I> ExactLen<I>
+where
+    I : Iterator<Item = u8>
+{
+
+    /// Creates a new [`ExactLen`], which yields no more than `len` bytes from `iter`.
+    #[inline(always)]
+    pub fn new(iter : I, len : usize) -> Self {
+        Self { iter, remaining : len }
+    }
+
+    /// Returns the inner iterator.
+    #[inline(always)]
+    pub fn into_inner(self) -> I { self.iter }
+
+}
+
+impl<I> Iterator for ExactLen<I>
+where
+    I : Iterator<Item = u8>
+{
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if (self.remaining == 0) { return None; }
+        let Some(b) = self.iter.next() else {
+            // The inner iterator ended early. Report the length as 0 from now on, so the `ExactSizeIterator` contract holds.
+            self.remaining = 0;
+            return None;
+        };
+        self.remaining -= 1;
+        Some(b)
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining),)
+    }
+}
+impl<I> ExactSizeIterator for ExactLen<I>
+where
+    I : Iterator<Item = u8>
+{ }
+
+
+impl<I> DecodeIter<ExactLen<I>>
+where
+    I : Iterator<Item = u8>
+{
+
+    /// Creates a new [`DecodeIter`] over the next `len` bytes of `iter`, which does not need to implement [`ExactSizeIterator`].
+    #[inline(always)]
+    pub fn with_len(iter : I, len : usize) -> Self {
+        Self::from(ExactLen::new(iter, len))
+    }
+
+}
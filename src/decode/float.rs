@@ -0,0 +1,84 @@
+//! `CanonicalF32` and `CanonicalF64` decoders.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+
+
+/// An `f32` with a canonical NaN.
+///
+/// Any NaN is replaced with [`NAN`](Self::NAN) when decoding and encoding, so that equal values always have equal bytes.
+/// A plain `f32` is en/decoded bit-exact.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Default, Debug)]
+pub struct CanonicalF32(pub f32);
+
+impl CanonicalF32 {
+
+    /// The canonical NaN, a quiet NaN with the bits `0x7FC00000`.
+    ///
+    /// [`f32::NAN`] is not used, as its bits are not guaranteed.
+    pub const NAN : f32 = f32::from_bits(0x7FC0_0000);
+
+    /// Returns `value`, with any NaN replaced with [`NAN`](Self::NAN).
+    #[inline(always)]
+    pub fn canonicalize(value : f32) -> f32 {
+        if (value.is_nan()) { Self::NAN } else { value }
+    }
+
+    /// Returns the inner value, with any NaN replaced with [`NAN`](Self::NAN).
+    #[inline(always)]
+    pub fn get(self) -> f32 { Self::canonicalize(self.0) }
+
+}
+
+impl PacketDecode for CanonicalF32 {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<f32>();
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(Self::canonicalize(f32::decode(iter)?))) }
+}
+
+
+/// An `f64` with a canonical NaN.
+///
+/// Any NaN is replaced with [`NAN`](Self::NAN) when decoding and encoding, so that equal values always have equal bytes.
+/// A plain `f64` is en/decoded bit-exact.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Default, Debug)]
+pub struct CanonicalF64(pub f64);
+
+impl CanonicalF64 {
+
+    /// The canonical NaN, a quiet NaN with the bits `0x7FF8000000000000`.
+    ///
+    /// [`f64::NAN`] is not used, as its bits are not guaranteed.
+    pub const NAN : f64 = f64::from_bits(0x7FF8_0000_0000_0000);
+
+    /// Returns `value`, with any NaN replaced with [`NAN`](Self::NAN).
+    #[inline(always)]
+    pub fn canonicalize(value : f64) -> f64 {
+        if (value.is_nan()) { Self::NAN } else { value }
+    }
+
+    /// Returns the inner value, with any NaN replaced with [`NAN`](Self::NAN).
+    #[inline(always)]
+    pub fn get(self) -> f64 { Self::canonicalize(self.0) }
+
+}
+
+impl PacketDecode for CanonicalF64 {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<f64>();
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(Self::canonicalize(f64::decode(iter)?))) }
+}
@@ -0,0 +1,58 @@
+//! `Le` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::ops::{ Deref, DerefMut };
+
+
+/// A number which is en/decoded in little-endian byte order.
+///
+/// The vanilla protocol is big-endian, which is what plain numbers use.
+/// Some protocols used by mods and proxies send little-endian numbers instead.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Le<T>(pub T);
+
+impl<T> From<T> for Le<T> {
+    #[inline(always)]
+    fn from(value : T) -> Self { Self(value) }
+}
+
+impl<T> Deref for Le<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> DerefMut for Le<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+
+macro impl_packetdecode_for_le($ty:ty) {
+    impl PacketDecode for Le<$ty> {
+        type Error = IncompleteDecodeError;
+        const MIN_ENCODED_LEN : usize = size_of::<$ty>();
+
+        #[inline]
+        fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+        where
+            I : ExactSizeIterator<Item = u8>
+        { Ok(Self(<$ty>::from_le_bytes(iter.read_arr()?))) }
+    }
+}
+
+impl_packetdecode_for_le!(u16);
+impl_packetdecode_for_le!(i16);
+impl_packetdecode_for_le!(u32);
+impl_packetdecode_for_le!(i32);
+impl_packetdecode_for_le!(u64);
+impl_packetdecode_for_le!(i64);
+impl_packetdecode_for_le!(u128);
+impl_packetdecode_for_le!(i128);
+impl_packetdecode_for_le!(f32);
+impl_packetdecode_for_le!(f64);
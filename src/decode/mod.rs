@@ -1,13 +1,49 @@
 //! Traits and data structures for decoding packets.
 
 
-use crate::meta::PacketMeta;
-use core::fmt::{ self, Display, Formatter };
+use crate::{
+    meta::{
+        PacketMeta,
+        PacketState
+    },
+    varint::{
+        VarInt,
+        VarIntDecodeError
+    }
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    mem::MaybeUninit
+};
+use alloc::vec::Vec;
 
 
 pub mod array;
+pub mod ascii;
+pub mod chain;
+pub mod checkpoint;
+pub mod either;
+pub mod elapsed;
+pub mod exact_len;
+pub mod float;
+pub mod le;
 mod num;
+pub mod option;
+pub mod optional;
+pub mod packed_bools;
+pub mod partial;
+pub mod range;
+mod refs;
+pub mod rest;
+pub mod result;
+pub mod set;
+pub mod slice;
+pub mod state;
 pub mod string;
+pub mod string_uuid;
+pub mod tristate;
+pub mod tuple;
+pub mod uuid_parts;
 pub mod vec;
 #[cfg(feature = "chrono")]
 pub mod datetime;
@@ -18,8 +54,10 @@ pub struct DecodeIter<I>
 where
     I : ExactSizeIterator<Item = u8>
 {
-    iter : I,
-    head : usize
+    iter      : I,
+    head      : usize,
+    depth     : usize,
+    max_depth : usize
 }
 
 impl<I> DecodeIter<I>
@@ -27,40 +65,137 @@ where
     I : ExactSizeIterator<Item = u8>
 {
 
+    /// Creates a new [`DecodeIter`], checking that `iter` has no more than `max_len` bytes.
+    ///
+    /// As the length of the iterator is known in advance, an oversized packet is rejected before any bytes are read.
+    /// Reads from the returned [`DecodeIter`] can never go beyond `max_len` bytes.
+    pub fn with_max_len(iter : I, max_len : usize) -> Result<Self, OversizedPacketError> {
+        let len = iter.len();
+        if (len > max_len) {
+            return Err(OversizedPacketError { len, max_len });
+        }
+        Ok(Self::from(iter))
+    }
+
+    /// Sets the maximum number of nested collections which can be decoded, replacing [`DEFAULT_MAX_DEPTH`].
+    #[inline(always)]
+    pub fn with_max_depth(mut self, max_depth : usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns the number of collections which are currently being decoded, one inside another.
+    #[inline(always)]
+    pub fn depth(&self) -> usize { self.depth }
+
+    /// Runs `f` one level deeper, returning an error instead if the maximum depth would be exceeded.
+    ///
+    /// Collection decoders use this, so that maliciously nested data can not overflow the stack.
+    /// Recursive types such as NBT compounds should use it too.
+    pub fn nested<T, E, F>(&mut self, f : F) -> Result<T, E>
+    where
+        E : From<DepthLimitExceededError>,
+        F : FnOnce(&mut Self) -> Result<T, E>
+    {
+        if (self.depth >= self.max_depth) {
+            return Err(E::from(DepthLimitExceededError { max_depth : self.max_depth }));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
     /// Returns the number of bytes which have been consumed.
     #[inline(always)]
     pub fn consumed(&self) -> usize { self.head }
 
+    /// Returns the number of bytes which have not yet been consumed.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize { self.iter.len() }
+
+    /// Returns an error if any bytes have not yet been consumed.
+    ///
+    /// Leftover bytes after a complete packet usually mean that the packet definition is wrong, or that the connection is out of sync.
+    #[inline]
+    pub fn expect_end(&self) -> Result<(), TrailingBytesError> {
+        match (self.remaining()) {
+            0         => Ok(()),
+            remaining => Err(TrailingBytesError { remaining })
+        }
+    }
+
     /// Reads a single byte from the iterator.
     ///
     /// This is similar to calling [`Iterator::next`], but returns a `Result` instead of an `Option`.
     pub fn read(&mut self) -> Result<u8, IncompleteDecodeError> {
-        let b = self.iter.next().ok_or(IncompleteDecodeError)?;
+        let b = self.iter.next().ok_or(IncompleteDecodeError::new(1, 0))?;
         self.head += 1;
         Ok(b)
     }
 
     /// Reads `count` bytes from the iterator into a vector.
+    ///
+    /// If fewer than `count` bytes remain, no bytes are consumed and nothing is allocated.
     pub fn read_vec(&mut self, count : usize) -> Result<Vec<u8>, IncompleteDecodeError> {
+        let available = self.iter.len();
+        if (available < count) {
+            return Err(IncompleteDecodeError::new(count, available));
+        }
         let mut buf = Vec::with_capacity(count);
-        for _ in 0..count { buf.push(self.iter.next().ok_or(IncompleteDecodeError)?); }
+        for i in 0..count {
+            // `ExactSizeIterator::len` is not guaranteed to be correct, so the iterator can still run out early.
+            let Some(b) = self.iter.next() else {
+                self.head += i;
+                return Err(IncompleteDecodeError::new(count, i));
+            };
+            buf.push(b);
+        }
         self.head += count;
         Ok(buf)
     }
 
     /// Reads `N` bytes from the iterator into an array.
+    ///
+    /// If fewer than `N` bytes remain, no bytes are consumed.
     #[inline(always)]
     pub fn read_arr<const N : usize>(&mut self) -> Result<[u8; N], IncompleteDecodeError> {
-        let b = self.iter.next_chunk::<N>().map_err(|_| IncompleteDecodeError)?;
+        let available = self.iter.len();
+        if (available < N) {
+            return Err(IncompleteDecodeError::new(N, available));
+        }
+        // `ExactSizeIterator::len` is not guaranteed to be correct, so the iterator can still run out early.
+        let b = self.iter.next_chunk::<N>().map_err(|partial| {
+            self.head += partial.len();
+            IncompleteDecodeError::new(N, partial.len())
+        })?;
         self.head += N;
         Ok(b)
     }
 
+    /// Reads `N` bytes from the iterator into an array, checking that enough bytes remain before reading any.
+    ///
+    /// Unlike [`DecodeIter::read_arr`], this fills the array with a plain loop, which is faster for large `N` with iterators that are not backed by a slice.
+    pub fn read_into_uninit<const N : usize>(&mut self) -> Result<[u8; N], IncompleteDecodeError> {
+        let available = self.iter.len();
+        if (available < N) {
+            return Err(IncompleteDecodeError::new(N, available));
+        }
+        let mut arr = [const { MaybeUninit::<u8>::uninit() }; N];
+        for (i, slot,) in arr.iter_mut().enumerate() {
+            // `ExactSizeIterator::len` is not guaranteed to be correct, so the iterator can still run out early.
+            slot.write(self.iter.next().ok_or(IncompleteDecodeError::new(N, i))?);
+        }
+        self.head += N;
+        // SAFETY: Every item in `arr` was written.
+        Ok(unsafe { MaybeUninit::array_assume_init(arr) })
+    }
+
     /// Reads enough bytes from the iterator to fill the buffer.
     pub fn read_buf(&mut self, buf : &mut [u8]) -> Result<(), IncompleteDecodeError> {
         for i in 0..buf.len() {
             // SAFETY: `i` is always less than `buf.len()`.
-            unsafe { *buf.get_unchecked_mut(i) = self.iter.next().ok_or(IncompleteDecodeError)?; }
+            unsafe { *buf.get_unchecked_mut(i) = self.iter.next().ok_or(IncompleteDecodeError::new(buf.len(), i))?; }
         }
         self.head += buf.len();
         Ok(())
@@ -68,11 +203,21 @@ where
 
     /// Skips the next `count` bytes in the iterator.
     pub fn skip(&mut self, count : usize) -> Result<(), IncompleteDecodeError> {
-        for _ in 0..count { self.iter.next().ok_or(IncompleteDecodeError)?; }
+        for i in 0..count { self.iter.next().ok_or(IncompleteDecodeError::new(count, i))?; }
         self.head += count;
         Ok(())
     }
 
+    /// Decodes a `T` from the iterator.
+    ///
+    /// This is the same as calling [`T::decode`](PacketDecode::decode), but can be chained on the iterator:
+    ///  `let x = iter.decode::<VarInt<i32>>()?;`.
+    #[inline(always)]
+    pub fn decode<T>(&mut self) -> Result<T, T::Error>
+    where
+        T : PacketDecode
+    { T::decode(self) }
+
 }
 
 impl<I> From<I> for DecodeIter<I>
@@ -81,7 +226,7 @@ where
 {
     #[inline(always)]
     fn from(iter : I) -> Self {
-        Self { iter, head : 0 }
+        Self { iter, head : 0, depth : 0, max_depth : DEFAULT_MAX_DEPTH }
     }
 }
 
@@ -114,6 +259,13 @@ where
     /// The error type returned when decoding fails.
     type Error;
 
+    /// The fewest bytes that a value of this type can be decoded from.
+    ///
+    /// Collections use this to limit how much space is reserved for a length prefix that was read from an untrusted packet,
+    ///  to no more items than the remaining bytes could hold.
+    /// It is only used as a capacity hint, so an underestimate is always allowed.
+    const MIN_ENCODED_LEN : usize = 1;
+
     /// Decode a value of this type from a byte iterator.
     fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
     where
@@ -121,6 +273,37 @@ where
 }
 
 
+/// Returns how many items of type `T` to reserve space for, for a collection with a decoded length of `length`.
+///
+/// This is no more than the number of items which could be decoded from the remaining bytes of `iter`,
+///  or for items which can be decoded from no bytes, no more than the number of remaining bytes.
+#[inline]
+pub(crate) fn capacity_hint<T, I>(iter : &DecodeIter<I>, length : usize) -> usize
+where
+    T : PacketDecode,
+    I : ExactSizeIterator<Item = u8>
+{ match (T::MIN_ENCODED_LEN) {
+    0   => length.min(iter.remaining()),
+    min => length.min(iter.remaining() / min)
+} }
+
+/// Decodes the length prefix of a collection of `T`s, checking that it is no greater than [`MAX_PREFIXED_LEN`].
+///
+/// If `T` can be decoded from no bytes, the length must also be no greater than the number of remaining bytes.
+/// Otherwise, a short packet with a huge length could make the decoder produce billions of empty items.
+pub(crate) fn decode_collection_len<T, I>(iter : &mut DecodeIter<I>) -> Result<usize, LengthDecodeError>
+where
+    T : PacketDecode,
+    I : ExactSizeIterator<Item = u8>
+{
+    let len = decode_prefixed_len(iter, MAX_PREFIXED_LEN)?;
+    if (T::MIN_ENCODED_LEN == 0 && len > iter.remaining()) {
+        return Err(LengthDecodeError::TooLong { len, max : iter.remaining() });
+    }
+    Ok(len)
+}
+
+
 /// A data structure which can be decoded from bytes.
 ///
 /// Unlike [`PacketDecode`], [`PrefixedPacketDecode`] should also include packet IDs in the decoding process.
@@ -137,11 +320,14 @@ where
         I : ExactSizeIterator<Item = u8>;
 }
 
+/// Decodes the packet ID, followed by the packet.
+///
+/// A single byte packet ID which runs out is reported as a [`Prefix`](PrefixedDecodeError::Prefix) error, the same as a [`VarInt`] one.
+/// The packet's own error type does not need to be convertible from an [`IncompleteDecodeError`],
+///  so packets such as [`varint_enum`](crate::varint_enum)s and tuples can be decoded with their packet ID too.
 impl<P> PrefixedPacketDecode for P
 where
-    P                                               : PacketDecode + PacketMeta,
-    <P as PacketDecode>::Error                      : From<IncompleteDecodeError>,
-    PrefixedDecodeError<<P as PacketDecode>::Error> : From<<P as PacketDecode>::Error>
+    P : PacketDecode + PacketMeta
 {
     type Error = PrefixedDecodeError<<P as PacketDecode>::Error>;
 
@@ -149,26 +335,161 @@ where
     where
         I : ExactSizeIterator<Item = u8>
     {
-        let prefix = iter.read()?;
+        #[cfg(feature = "observer")]
+        let start  = iter.consumed();
+        let prefix = if (<P as PacketMeta>::VARINT_PREFIX) {
+            *VarInt::<u32>::decode(iter).map_err(PrefixedDecodeError::Prefix)?
+        } else {
+            iter.read().map_err(|err| PrefixedDecodeError::Prefix(VarIntDecodeError::Incomplete(err)))? as u32
+        };
         if (prefix == <P as PacketMeta>::PREFIX) {
-            Ok(<P as PacketDecode>::decode(iter)?)
+            let packet = <P as PacketDecode>::decode(iter).map_err(PrefixedDecodeError::Error)?;
+            #[cfg(feature = "observer")]
+            crate::observer::observe_decoded::<P>(iter.consumed() - start);
+            Ok(packet)
         } else {
             Err(PrefixedDecodeError::UnknownPrefix {
                 found    : prefix,
-                expected : Some(<P as PacketMeta>::PREFIX)
+                expected : Some(<P as PacketMeta>::PREFIX),
+                state    : None,
+                label    : <P as PacketMeta>::LABEL
             })
         }
     }
 }
 
 
-/// The byte iterator did not provide enough data to fully decode a value.
+/// The largest length which the protocol allows for a length prefixed value.
+///
+/// Lengths are [`VarInt`]s which must not be negative when read as an `i32`.
+pub const MAX_PREFIXED_LEN : usize = i32::MAX as usize;
+
+/// Decodes a [`VarInt::<u32>`](VarInt) length prefix, checking that it is no greater than `max`.
+///
+/// All length prefixed collection decoders use this, so that they handle bad lengths in the same way.
+pub fn decode_prefixed_len<I>(iter : &mut DecodeIter<I>, max : usize) -> Result<usize, LengthDecodeError>
+where
+    I : ExactSizeIterator<Item = u8>
+{
+    let len = *VarInt::<u32>::decode(iter).map_err(LengthDecodeError::VarInt)? as usize;
+    if (len > max) {
+        return Err(LengthDecodeError::TooLong { len, max });
+    }
+    Ok(len)
+}
+
+
+/// Returned by [`decode_prefixed_len`] when a length prefix was not decoded successfully.
 #[derive(Debug)]
-pub struct IncompleteDecodeError;
+pub enum LengthDecodeError {
+    /// The length failed to decode.
+    VarInt(VarIntDecodeError),
+    /// The decoded length was greater than the maximum allowed length.
+    TooLong {
+        /// The decoded length.
+        len : usize,
+        /// The maximum allowed length.
+        max : usize
+    }
+}
+impl From<IncompleteDecodeError> for LengthDecodeError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::VarInt(VarIntDecodeError::Incomplete(err)) }
+}
+impl From<VarIntDecodeError> for LengthDecodeError {
+    #[inline(always)]
+    fn from(err : VarIntDecodeError) -> Self { Self::VarInt(err) }
+}
+impl Display for LengthDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::VarInt(err)          => err.fmt(f),
+        Self::TooLong { len, max } => write!(f, "too long ({len} > {max})")
+    } }
+}
+
+
+/// The byte iterator did not provide enough data to fully decode a value.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct IncompleteDecodeError {
+    /// The number of bytes which were needed, if known.
+    pub needed    : Option<usize>,
+    /// The number of bytes which were available, if known.
+    pub available : Option<usize>
+}
+
+impl IncompleteDecodeError {
+
+    /// Creates a new [`IncompleteDecodeError`] where `needed` bytes were needed, but only `available` bytes were available.
+    #[inline(always)]
+    pub const fn new(needed : usize, available : usize) -> Self {
+        Self { needed : Some(needed), available : Some(available) }
+    }
+
+    /// Creates a new [`IncompleteDecodeError`] where the number of bytes needed is not known.
+    #[inline(always)]
+    pub const fn unknown() -> Self {
+        Self { needed : None, available : None }
+    }
+
+}
 
 impl Display for IncompleteDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self { needed : Some(needed), available : Some(available) } => write!(f, "missing bytes (needed {needed}, {available} available)"),
+        Self { needed : Some(needed), available : None            } => write!(f, "missing bytes (needed {needed})"),
+        Self { needed : None,         ..                          } => write!(f, "missing bytes")
+    } }
+}
+impl core::error::Error for IncompleteDecodeError { }
+/// Converts into an [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) error.
+#[cfg(feature = "std")]
+impl From<IncompleteDecodeError> for std::io::Error {
+    #[inline]
+    fn from(err : IncompleteDecodeError) -> Self { Self::new(std::io::ErrorKind::UnexpectedEof, err) }
+}
+
+
+/// The maximum number of nested collections that a [`DecodeIter`] will decode, unless [`DecodeIter::with_max_depth`] is used.
+pub const DEFAULT_MAX_DEPTH : usize = 512;
+
+/// Collections were nested more deeply than the maximum allowed depth.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DepthLimitExceededError {
+    /// The maximum allowed depth.
+    pub max_depth : usize
+}
+
+impl Display for DepthLimitExceededError {
     #[inline(always)]
-    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "missing bytes") }
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "nested too deeply (> {})", self.max_depth) }
+}
+
+
+/// The byte iterator provided more data than the maximum packet length.
+#[derive(Debug)]
+pub struct OversizedPacketError {
+    /// The number of bytes in the packet.
+    pub len     : usize,
+    /// The maximum number of bytes allowed.
+    pub max_len : usize
+}
+
+impl Display for OversizedPacketError {
+    #[inline(always)]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "packet too large ({} > {})", self.len, self.max_len) }
+}
+
+
+/// The byte iterator had bytes left over after a value was fully decoded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TrailingBytesError {
+    /// The number of bytes which were not consumed.
+    pub remaining : usize
+}
+
+impl Display for TrailingBytesError {
+    #[inline(always)]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "{} trailing bytes", self.remaining) }
 }
 
 
@@ -181,16 +502,39 @@ pub enum PrefixedDecodeError<E> {
     /// The packet ID is not recognised.
     UnknownPrefix {
         /// The ID of the decoded packet.
-        found    : u8,
+        found    : u32,
         /// The supported packet ID.
         ///If there are multiple support packet IDs, this will be `None`.
-        expected : Option<u8>
+        expected : Option<u32>,
+        /// The state that the packet was decoded in.
+        /// If the decoder was not aware of the state, this will be `None`.
+        state    : Option<PacketState>,
+        /// The [`PacketMeta::LABEL`] of the expected packet.
+        label    : &'static str
     },
 
+    /// The packet ID failed to decode.
+    ///
+    /// Single byte packet IDs can only fail with [`VarIntDecodeError::Incomplete`].
+    Prefix(VarIntDecodeError),
+
     /// Some other error occured.
     Error(E)
 }
 
+impl<E> PrefixedDecodeError<E> {
+
+    /// Attaches the state that the packet was decoded in to an [`UnknownPrefix`](Self::UnknownPrefix) error.
+    ///
+    /// Other errors are returned unchanged.
+    #[inline]
+    pub fn with_state(self, state : PacketState) -> Self { match (self) {
+        Self::UnknownPrefix { found, expected, label, .. } => Self::UnknownPrefix { found, expected, state : Some(state), label },
+        err                                                => err
+    } }
+
+}
+
 impl<E> From<IncompleteDecodeError> for PrefixedDecodeError<E>
 where
     E : From<IncompleteDecodeError>
@@ -200,3 +544,18 @@ where
         Self::Error(E::from(err))
     }
 }
+impl<E> Display for PrefixedDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::UnknownPrefix { found, expected, state, label } => {
+            write!(f, "unknown prefix {found:#04x}")?;
+            if let Some(expected) = expected { write!(f, " (expected {expected:#04x} for {label})")?; }
+            if let Some(state) = state { write!(f, " in state {state:?}")?; }
+            Ok(())
+        },
+        Self::Prefix(err) => write!(f, "prefix {err}"),
+        Self::Error(err)  => err.fmt(f)
+    } }
+}
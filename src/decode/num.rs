@@ -3,12 +3,14 @@ use crate::decode::{
     DecodeIter,
     IncompleteDecodeError
 };
+use core::num::Wrapping;
 use uuid::Uuid;
 
 
 macro impl_packetdecode_for_num($ty:ty) {
     impl PacketDecode for $ty {
         type Error = IncompleteDecodeError;
+        const MIN_ENCODED_LEN : usize = size_of::<Self>();
 
         fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
         where
@@ -43,6 +45,7 @@ impl PacketDecode for bool {
 
 impl PacketDecode for Uuid {
     type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<u128>();
 
     #[inline(always)]
     fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
@@ -50,3 +53,18 @@ impl PacketDecode for Uuid {
         I : ExactSizeIterator<Item = u8>
     { Ok(Uuid::from_u128(<_>::decode(iter)?)) }
 }
+
+/// Decodes the inner value, with no change to its encoding.
+impl<T> PacketDecode for Wrapping<T>
+where
+    T : PacketDecode
+{
+    type Error = T::Error;
+    const MIN_ENCODED_LEN : usize = T::MIN_ENCODED_LEN;
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Wrapping(T::decode(iter)?)) }
+}
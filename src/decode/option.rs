@@ -0,0 +1,61 @@
+//! `Option<T>` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    DepthLimitExceededError,
+    IncompleteDecodeError
+};
+use core::fmt::{ self, Display, Formatter };
+
+
+/// Decodes a `bool` tag, followed by a `T` if the tag is `true`.
+///
+/// Recursive structures can use `Option<Box<T>>` for optional children.
+/// The value is decoded one level deeper with [`DecodeIter::nested`], so that deeply nested packets from untrusted peers can not overflow the stack.
+impl<T> PacketDecode for Option<T>
+where
+    T : PacketDecode
+{
+    type Error = OptionDecodeError<T::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        if (bool::decode(iter)?) {
+            iter.nested(|iter| Ok(Some(T::decode(iter).map_err(OptionDecodeError::Value)?)))
+        } else { Ok(None) }
+    }
+}
+
+
+/// Returned by packet decoders when an `Option<T>` was not decoded successfully.
+#[derive(Debug)]
+pub enum OptionDecodeError<E> {
+    /// The tag failed to decode.
+    Tag(IncompleteDecodeError),
+    /// Values were nested too deeply.
+    Depth(DepthLimitExceededError),
+    /// The value failed to decode.
+    Value(E)
+}
+impl<E> From<DepthLimitExceededError> for OptionDecodeError<E> {
+    #[inline(always)]
+    fn from(err : DepthLimitExceededError) -> Self { Self::Depth(err) }
+}
+impl<E> From<IncompleteDecodeError> for OptionDecodeError<E> {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Tag(err) }
+}
+impl<E> Display for OptionDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Tag(err)   => write!(f, "tag {err}"),
+        Self::Depth(err) => err.fmt(f),
+        Self::Value(err) => write!(f, "value {err}")
+    } }
+}
@@ -0,0 +1,76 @@
+//! `OptionalVarInt<T>` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use crate::varint::{
+    VarInt,
+    VarIntDecodeError
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
+};
+
+
+/// An optional value, prefixed with a [`VarInt::<u32>`](VarInt) count of `0` or `1`.
+///
+/// A count of `1` is followed by a `T`, and a count of `0` is followed by nothing.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct OptionalVarInt<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for OptionalVarInt<T> {
+    #[inline(always)]
+    fn from(value : Option<T>) -> Self { Self(value) }
+}
+
+impl<T> Deref for OptionalVarInt<T> {
+    type Target = Option<T>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> PacketDecode for OptionalVarInt<T>
+where
+    T : PacketDecode
+{
+    type Error = OptionalVarIntDecodeError<T::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { match (*VarInt::<u32>::decode(iter).map_err(OptionalVarIntDecodeError::Count)?) {
+        0     => Ok(Self(None)),
+        1     => Ok(Self(Some(T::decode(iter).map_err(OptionalVarIntDecodeError::Value)?))),
+        count => Err(OptionalVarIntDecodeError::TooMany(count))
+    } }
+}
+
+
+/// Returned by packet decoders when an `OptionalVarInt<T>` was not decoded successfully.
+#[derive(Debug)]
+pub enum OptionalVarIntDecodeError<E> {
+    /// The count failed to decode.
+    Count(VarIntDecodeError),
+    /// The count was greater than `1`.
+    TooMany(u32),
+    /// The value failed to decode.
+    Value(E)
+}
+impl<E> From<IncompleteDecodeError> for OptionalVarIntDecodeError<E> {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Count(VarIntDecodeError::Incomplete(err)) }
+}
+impl<E> Display for OptionalVarIntDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Count(err)     => write!(f, "count {err}"),
+        Self::TooMany(count) => write!(f, "too many ({count} > 1)"),
+        Self::Value(err)     => write!(f, "value {err}")
+    } }
+}
@@ -0,0 +1,68 @@
+//! `PackedBools<N>` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::ops::{ Deref, DerefMut };
+
+
+/// `N` flags, packed into `N.div_ceil(8)` bytes.
+///
+/// The bits are in LSB-first order: flag `i` is bit `i % 8` of byte `i / 8`, where bit `0` is the least significant.
+/// Unused bits in the last byte are written as `0`, and ignored when decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PackedBools<const N : usize>(pub [bool; N]);
+
+impl<const N : usize> PackedBools<N> {
+
+    /// The number of bytes that `N` flags are packed into.
+    pub const BYTES : usize = N.div_ceil(8);
+
+}
+
+impl<const N : usize> Default for PackedBools<N> {
+    #[inline(always)]
+    fn default() -> Self { Self([false; N]) }
+}
+
+impl<const N : usize> From<[bool; N]> for PackedBools<N> {
+    #[inline(always)]
+    fn from(value : [bool; N]) -> Self { Self(value) }
+}
+
+impl<const N : usize> Deref for PackedBools<N> {
+    type Target = [bool; N];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<const N : usize> DerefMut for PackedBools<N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<const N : usize> PacketDecode for PackedBools<N> {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = Self::BYTES;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let available = iter.remaining();
+        if (available < Self::BYTES) {
+            return Err(IncompleteDecodeError::new(Self::BYTES, available));
+        }
+        let mut flags = [false; N];
+        for chunk in flags.chunks_mut(8) {
+            let byte = iter.read()?;
+            for (bit, flag,) in chunk.iter_mut().enumerate() {
+                *flag = ((byte >> bit) & 1) != 0;
+            }
+        }
+        Ok(Self(flags))
+    }
+}
@@ -0,0 +1,132 @@
+//! A resumable decoder for length prefixed frames which arrive in pieces.
+
+
+use crate::decode::{
+    DecodeIter,
+    PrefixedPacketDecode,
+    LengthDecodeError,
+    MAX_PREFIXED_LEN
+};
+use crate::varint::{
+    self,
+    VarIntDecodeError
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    marker::PhantomData,
+    task::Poll
+};
+use alloc::vec::Vec;
+
+
+/// Buffers bytes until a whole frame is available, then decodes it as a `P`.
+///
+/// A frame is a [`VarInt::<u32>`](crate::varint::VarInt) length, followed by that many bytes, as written by [`EncodeBuf::new_len_prefixed`](crate::encode::EncodeBuf::new_len_prefixed).
+/// The length is only decoded once per frame, no matter how many pieces the frame arrives in.
+pub struct PartialDecoder<P>
+where
+    P : PrefixedPacketDecode
+{
+    buf     : Vec<u8>,
+    /// The position in `buf` of the first byte which has not been decoded yet.
+    ///
+    /// Decoded frames are only removed from the front of `buf` when more bytes are pushed, so that decoding many small frames is not quadratic.
+    offset  : usize,
+    /// The length of the length prefix and the length of the frame contents, once known.
+    frame   : Option<(usize, usize,)>,
+    max_len : usize,
+    marker  : PhantomData<fn() -> P>
+}
+
+impl<P> PartialDecoder<P>
+where
+    P : PrefixedPacketDecode
+{
+
+    /// Creates a new, empty [`PartialDecoder`].
+    #[inline]
+    pub fn new() -> Self { Self::with_max_len(MAX_PREFIXED_LEN) }
+
+    /// Creates a new, empty [`PartialDecoder`] which rejects frames longer than `max_len` bytes.
+    ///
+    /// Oversized frames are rejected as soon as their length is known, before the rest of the frame is buffered.
+    #[inline]
+    pub fn with_max_len(max_len : usize) -> Self {
+        Self { buf : Vec::new(), offset : 0, frame : None, max_len, marker : PhantomData }
+    }
+
+    /// Returns the number of bytes which have been buffered, but not yet decoded.
+    #[inline(always)]
+    pub fn buffered(&self) -> usize { self.buf.len() - self.offset }
+
+    /// Buffers more bytes.
+    #[inline]
+    pub fn push(&mut self, bytes : &[u8]) {
+        if (self.offset == self.buf.len()) {
+            self.buf.clear();
+            self.offset = 0;
+        } else if (self.offset > self.buf.len() / 2) {
+            self.buf.drain(..self.offset);
+            self.offset = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next frame if it has been fully buffered, or returns [`Poll::Pending`] if more bytes are needed.
+    ///
+    /// After an error is returned, the contents of the buffer are unspecified, and the decoder should be discarded.
+    pub fn poll_decode(&mut self) -> Poll<Result<P, PartialDecodeError<P::Error>>> {
+        let (header_len, len,) = match (self.frame) {
+            Some(frame) => frame,
+            None        => {
+                let (len, header_len,) = match (varint::decode_u32(self.buf[self.offset..].iter().copied())) {
+                    Ok(decoded)                           => decoded,
+                    Err(VarIntDecodeError::Incomplete(_)) => { return Poll::Pending; },
+                    Err(err)                              => { return Poll::Ready(Err(PartialDecodeError::Length(LengthDecodeError::VarInt(err)))); }
+                };
+                let len = len as usize;
+                if (len > self.max_len) {
+                    return Poll::Ready(Err(PartialDecodeError::Length(LengthDecodeError::TooLong { len, max : self.max_len })));
+                }
+                *self.frame.insert((header_len, len,))
+            }
+        };
+        let frame_start = self.offset + header_len;
+        let frame_end   = frame_start + len;
+        if (self.buf.len() < frame_end) {
+            return Poll::Pending;
+        }
+        self.frame = None;
+        let result = P::decode_prefixed(&mut DecodeIter::from(self.buf[frame_start..frame_end].iter().copied()));
+        self.offset = frame_end;
+        Poll::Ready(result.map_err(PartialDecodeError::Packet))
+    }
+
+}
+
+impl<P> Default for PartialDecoder<P>
+where
+    P : PrefixedPacketDecode
+{
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+
+/// Returned by [`PartialDecoder::poll_decode`] when a frame was not decoded successfully.
+#[derive(Debug)]
+pub enum PartialDecodeError<E> {
+    /// The length of the frame failed to decode.
+    Length(LengthDecodeError),
+    /// The packet in the frame failed to decode.
+    Packet(E)
+}
+impl<E> Display for PartialDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Length(err) => write!(f, "length {err}"),
+        Self::Packet(err) => err.fmt(f)
+    } }
+}
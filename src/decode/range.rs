@@ -0,0 +1,89 @@
+//! `Range<T>` and `RangeInclusive<T>` decoders.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::{
+    cmp::Ordering,
+    fmt::{ self, Display, Formatter },
+    ops::{ Range, RangeInclusive }
+};
+
+
+impl<T> PacketDecode for Range<T>
+where
+    T : PacketDecode + PartialOrd
+{
+    type Error = RangeDecodeError<T::Error>;
+    const MIN_ENCODED_LEN : usize = T::MIN_ENCODED_LEN.saturating_mul(2);
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let (start, end,) = decode_bounds(iter)?;
+        Ok(start..end)
+    }
+}
+
+impl<T> PacketDecode for RangeInclusive<T>
+where
+    T : PacketDecode + PartialOrd
+{
+    type Error = RangeDecodeError<T::Error>;
+    const MIN_ENCODED_LEN : usize = T::MIN_ENCODED_LEN.saturating_mul(2);
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let (start, end,) = decode_bounds(iter)?;
+        Ok(start..=end)
+    }
+}
+
+fn decode_bounds<I, T>(iter : &mut DecodeIter<I>) -> Result<(T, T,), RangeDecodeError<T::Error>>
+where
+    I : ExactSizeIterator<Item = u8>,
+    T : PacketDecode + PartialOrd
+{
+    let start = T::decode(iter).map_err(RangeDecodeError::Start)?;
+    let end   = T::decode(iter).map_err(RangeDecodeError::End)?;
+    // Incomparable bounds, such as NaN, are also rejected.
+    if (! matches!(start.partial_cmp(&end), Some(Ordering::Less | Ordering::Equal))) {
+        return Err(RangeDecodeError::Inverted);
+    }
+    Ok((start, end,))
+}
+
+
+/// Returned by packet decoders when a `Range<T>` or `RangeInclusive<T>` was not decoded successfully.
+#[derive(Debug)]
+pub enum RangeDecodeError<E> {
+    /// The start of the range failed to decode.
+    Start(E),
+    /// The end of the range failed to decode.
+    End(E),
+    /// The start of the range was greater than the end.
+    Inverted
+}
+impl<E> From<IncompleteDecodeError> for RangeDecodeError<E>
+where
+    E : From<IncompleteDecodeError>
+{
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Start(E::from(err)) }
+}
+impl<E> Display for RangeDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Start(err) => write!(f, "start {err}"),
+        Self::End(err)   => write!(f, "end {err}"),
+        Self::Inverted   => write!(f, "start is greater than end")
+    } }
+}
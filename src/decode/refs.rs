@@ -0,0 +1,24 @@
+use crate::decode::{
+    PacketDecode,
+    DecodeIter
+};
+use alloc::boxed::Box;
+
+
+/// Decodes a `T` on the heap.
+///
+/// This does not add a level of [`DecodeIter::nested`] itself. Recursive types get their depth limit from the
+///  `Option<T>` or collection that ends the recursion.
+impl<T> PacketDecode for Box<T>
+where
+    T : PacketDecode
+{
+    type Error = T::Error;
+    const MIN_ENCODED_LEN : usize = T::MIN_ENCODED_LEN;
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { T::decode(iter).map(Box::new) }
+}
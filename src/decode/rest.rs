@@ -0,0 +1,39 @@
+//! `RestOfPacket` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::ops::Deref;
+use alloc::vec::Vec;
+
+
+/// All bytes remaining in the packet, without a [`VarInt`](crate::varint::VarInt) length.
+///
+/// This must be the last field of a packet.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct RestOfPacket(pub Vec<u8>);
+
+impl From<Vec<u8>> for RestOfPacket {
+    #[inline(always)]
+    fn from(value : Vec<u8>) -> Self { Self(value) }
+}
+
+impl Deref for RestOfPacket {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for RestOfPacket {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = 0;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(iter.read_vec(iter.remaining())?)) }
+}
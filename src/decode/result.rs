@@ -0,0 +1,58 @@
+//! `Result<T, E>` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::fmt::{ self, Display, Formatter };
+
+
+/// Decodes a `u8` tag of `0` followed by a `T`, or a tag of `1` followed by an `E`.
+impl<T, E> PacketDecode for Result<T, E>
+where
+    T : PacketDecode,
+    E : PacketDecode
+{
+    type Error = ResultDecodeError<T::Error, E::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { match (iter.read()?) {
+        0   => Ok(Ok(T::decode(iter).map_err(ResultDecodeError::Ok)?)),
+        1   => Ok(Err(E::decode(iter).map_err(ResultDecodeError::Err)?)),
+        tag => Err(ResultDecodeError::InvalidTag(tag))
+    } }
+}
+
+
+/// Returned by packet decoders when a `Result<T, E>` was not decoded successfully.
+#[derive(Debug)]
+pub enum ResultDecodeError<T, E> {
+    /// The tag failed to decode.
+    Tag(IncompleteDecodeError),
+    /// The tag was not `0` or `1`.
+    InvalidTag(u8),
+    /// The `Ok` value failed to decode.
+    Ok(T),
+    /// The `Err` value failed to decode.
+    Err(E)
+}
+impl<T, E> From<IncompleteDecodeError> for ResultDecodeError<T, E> {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Tag(err) }
+}
+impl<T, E> Display for ResultDecodeError<T, E>
+where
+    T : Display,
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Tag(err)        => write!(f, "tag {err}"),
+        Self::InvalidTag(tag) => write!(f, "invalid tag {tag}"),
+        Self::Ok(err)         => write!(f, "ok {err}"),
+        Self::Err(err)        => write!(f, "err {err}")
+    } }
+}
@@ -0,0 +1,111 @@
+//! `HashSet<T>` and `BTreeSet<T>` decoders.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    DepthLimitExceededError,
+    LengthDecodeError,
+    decode_collection_len
+};
+use core::fmt::{ self, Display, Formatter };
+#[cfg(feature = "std")]
+use core::hash::{ BuildHasher, Hash };
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+
+/// Decodes a set.
+///
+/// If the same item appears more than once, this returns [`SetDecodeError::Duplicate`] instead of silently deduplicating.
+#[cfg(feature = "std")]
+impl<T, S> PacketDecode for HashSet<T, S>
+where
+    T : PacketDecode + Eq + Hash,
+    S : BuildHasher + Default
+{
+    type Error = SetDecodeError<T::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        iter.nested(|iter| {
+            let     length = decode_collection_len::<T, _>(iter).map_err(SetDecodeError::Length)?;
+            let mut set    = HashSet::with_capacity_and_hasher(crate::decode::capacity_hint::<T, _>(iter, length), S::default());
+            for i in 0..length {
+                let item = T::decode(iter).map_err(|err| SetDecodeError::Item { index : i, err })?;
+                if (! set.insert(item)) {
+                    return Err(SetDecodeError::Duplicate { index : i });
+                }
+            }
+            Ok(set)
+        })
+    }
+}
+
+
+/// Decodes a set.
+///
+/// If the same item appears more than once, this returns [`SetDecodeError::Duplicate`] instead of silently deduplicating.
+impl<T> PacketDecode for BTreeSet<T>
+where
+    T : PacketDecode + Ord
+{
+    type Error = SetDecodeError<T::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        iter.nested(|iter| {
+            let     length = decode_collection_len::<T, _>(iter).map_err(SetDecodeError::Length)?;
+            let mut set    = BTreeSet::new();
+            for i in 0..length {
+                let item = T::decode(iter).map_err(|err| SetDecodeError::Item { index : i, err })?;
+                if (! set.insert(item)) {
+                    return Err(SetDecodeError::Duplicate { index : i });
+                }
+            }
+            Ok(set)
+        })
+    }
+}
+
+
+/// Returned by packet decoders when a `HashSet<T>` or `BTreeSet<T>` was not decoded successfully.
+#[derive(Debug)]
+pub enum SetDecodeError<E> {
+    /// The length of the set failed to decode.
+    Length(LengthDecodeError),
+    /// Collections were nested too deeply.
+    Depth(DepthLimitExceededError),
+    /// An item in the set could not be decoded.
+    Item {
+        /// The index of the item that was not decoded.
+        index : usize,
+        /// The error.
+        err   : E
+    },
+    /// An item in the set is equal to an item before it.
+    Duplicate {
+        /// The index of the repeated item.
+        index : usize
+    }
+}
+impl<E> From<DepthLimitExceededError> for SetDecodeError<E> {
+    #[inline(always)]
+    fn from(err : DepthLimitExceededError) -> Self { Self::Depth(err) }
+}
+impl<E> Display for SetDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Length(err)          => write!(f, "length {err}"),
+        Self::Depth(err)           => err.fmt(f),
+        Self::Item { index, err } => write!(f, "item {index} {err}"),
+        Self::Duplicate { index } => write!(f, "item {index} duplicate")
+    } }
+}
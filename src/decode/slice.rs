@@ -0,0 +1,109 @@
+//! A decoder over a contiguous byte slice.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError,
+    LengthDecodeError,
+    string::StringDecodeError
+};
+use crate::varint::VarInt;
+use alloc::string::String;
+
+
+/// A decoder over a contiguous byte slice.
+///
+/// Unlike [`DecodeIter`], this can borrow from the bytes of the packet instead of copying them.
+#[derive(Clone, Debug)]
+pub struct SliceDecoder<'l> {
+    bytes : &'l [u8],
+    head  : usize
+}
+
+impl<'l> SliceDecoder<'l> {
+
+    /// Creates a new [`SliceDecoder`] over `bytes`.
+    #[inline(always)]
+    pub fn new(bytes : &'l [u8]) -> Self {
+        Self { bytes, head : 0 }
+    }
+
+    /// Returns the number of bytes which have been consumed.
+    #[inline(always)]
+    pub fn consumed(&self) -> usize { self.head }
+
+    /// Returns the number of bytes which have not yet been consumed.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize { self.bytes.len() - self.head }
+
+    /// Returns the bytes which have not yet been consumed.
+    #[inline(always)]
+    pub fn remaining_slice(&self) -> &'l [u8] {
+        // SAFETY: `self.head` is never greater than `self.bytes.len()`.
+        unsafe { self.bytes.get_unchecked(self.head..) }
+    }
+
+    /// Decodes a value from the bytes which have not yet been consumed.
+    pub fn decode<T>(&mut self) -> Result<T, T::Error>
+    where
+        T : PacketDecode
+    {
+        let mut iter  = DecodeIter::from(self.remaining_slice().iter().copied());
+        let     value = T::decode(&mut iter);
+        self.head += iter.consumed();
+        value
+    }
+
+    /// Reads `count` bytes, borrowing them from the slice.
+    pub fn read_slice(&mut self, count : usize) -> Result<&'l [u8], IncompleteDecodeError> {
+        let bytes = self.remaining_slice().get(..count).ok_or(IncompleteDecodeError::new(count, self.remaining()))?;
+        self.head += count;
+        Ok(bytes)
+    }
+
+    /// Reads a [`VarInt`] length prefixed string, borrowing it from the slice.
+    pub fn read_str(&mut self) -> Result<&'l str, StringDecodeError> {
+        let length = *self.decode::<VarInt<u32>>().map_err(|err| StringDecodeError::Length(LengthDecodeError::VarInt(err)))? as usize;
+        let bytes  = self.read_slice(length)?;
+        // A `FromUtf8Error` is only built (and allocated) if the bytes are invalid.
+        str::from_utf8(bytes).map_err(|_| StringDecodeError::Utf8(String::from_utf8(bytes.to_vec()).unwrap_err()))
+    }
+
+    /// Saves the current position, so that it can be returned to with [`restore`](Self::restore).
+    ///
+    /// This lets one packet shape be tried, and another tried from the same position if it fails.
+    #[inline(always)]
+    pub fn checkpoint(&self) -> SliceCheckpoint { SliceCheckpoint { head : self.head } }
+
+    /// Returns to a position saved by [`checkpoint`](Self::checkpoint), rewinding [`consumed`](Self::consumed).
+    ///
+    /// ### Panics
+    /// Panics if `checkpoint` is beyond the end of the slice, which can only happen if it was taken from a different [`SliceDecoder`].
+    #[inline]
+    pub fn restore(&mut self, checkpoint : SliceCheckpoint) {
+        assert!(checkpoint.head <= self.bytes.len(), "`SliceCheckpoint` is beyond the end of the slice");
+        self.head = checkpoint.head;
+    }
+
+}
+
+impl<'l> From<&'l [u8]> for SliceDecoder<'l> {
+    #[inline(always)]
+    fn from(bytes : &'l [u8]) -> Self { Self::new(bytes) }
+}
+
+
+/// A saved position of a [`SliceDecoder`], which can be returned to with [`SliceDecoder::restore`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SliceCheckpoint {
+    head : usize
+}
+
+impl SliceCheckpoint {
+
+    /// Returns the number of bytes which had been consumed when this checkpoint was taken.
+    #[inline(always)]
+    pub fn consumed(&self) -> usize { self.head }
+
+}
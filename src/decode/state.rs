@@ -0,0 +1,99 @@
+//! `StateScoped<STATE, P>` decoder, and decoding in a runtime state.
+
+
+use crate::decode::{
+    PrefixedPacketDecode,
+    PrefixedDecodeError,
+    DecodeIter
+};
+use crate::meta::{
+    PacketMeta,
+    PacketState
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
+};
+
+
+/// A packet which may only be decoded while the connection is in state `STATE`.
+///
+/// Decoding fails with [`StateScopedDecodeError::StateMismatch`] if the packet's [`PacketMeta::STATE`] is not `STATE`.
+/// Any [`PrefixedDecodeError::UnknownPrefix`] errors will have `STATE` attached.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct StateScoped<const STATE : PacketState, P>(pub P);
+
+impl<const STATE : PacketState, P> StateScoped<STATE, P> {
+
+    /// Returns the inner packet.
+    #[inline(always)]
+    pub fn into_inner(self) -> P { self.0 }
+
+}
+
+impl<const STATE : PacketState, P> Deref for StateScoped<STATE, P> {
+    type Target = P;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<const STATE : PacketState, P, E> PrefixedPacketDecode for StateScoped<STATE, P>
+where
+    P : PrefixedPacketDecode<Error = PrefixedDecodeError<E>> + PacketMeta
+{
+    type Error = StateScopedDecodeError<PrefixedDecodeError<E>>;
+
+    fn decode_prefixed<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        decode_prefixed_in_state(STATE, iter).map(Self)
+    }
+}
+
+
+/// Decodes a packet with [`PrefixedPacketDecode`], if the connection is currently in `state`.
+///
+/// This is the same as decoding a [`StateScoped`], for connections which only know their state at runtime.
+/// Packets in different states can share a prefix, such as `0x00` in both [`Status`](PacketState::Status) and [`Login`](PacketState::Login),
+///  so this should be used wherever a prefix alone does not identify the packet.
+///
+/// Fails with [`StateScopedDecodeError::StateMismatch`] without consuming any bytes if the packet's [`PacketMeta::STATE`] is not `state`.
+/// Any [`PrefixedDecodeError::UnknownPrefix`] errors will have `state` attached.
+pub fn decode_prefixed_in_state<P, E, I>(state : PacketState, iter : &mut DecodeIter<I>) -> Result<P, StateScopedDecodeError<PrefixedDecodeError<E>>>
+where
+    P : PrefixedPacketDecode<Error = PrefixedDecodeError<E>> + PacketMeta,
+    I : ExactSizeIterator<Item = u8>
+{
+    if (<P as PacketMeta>::STATE != state) {
+        return Err(StateScopedDecodeError::StateMismatch {
+            expected : state,
+            found    : <P as PacketMeta>::STATE
+        });
+    }
+    P::decode_prefixed(iter).map_err(|err| StateScopedDecodeError::Error(err.with_state(state)))
+}
+
+
+/// Returned by packet decoders when a `StateScoped<STATE, P>` was not decoded successfully.
+#[derive(Debug)]
+pub enum StateScopedDecodeError<E> {
+    /// The packet can not be sent in the current state.
+    StateMismatch {
+        /// The current state.
+        expected : PacketState,
+        /// The state of the packet.
+        found    : PacketState
+    },
+    /// Some other error occured.
+    Error(E)
+}
+impl<E> Display for StateScopedDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::StateMismatch { expected, found } => write!(f, "state mismatch (expected {expected:?}, found {found:?})"),
+        Self::Error(err)                        => err.fmt(f)
+    } }
+}
@@ -1,52 +1,181 @@
-//! `String` decoder.
+//! `String` and `Box<str>` decoders.
 
 
 use crate::decode::{
     PacketDecode,
     DecodeIter,
-    IncompleteDecodeError
+    IncompleteDecodeError,
+    LengthDecodeError,
+    decode_prefixed_len
 };
-use crate::varint::{
-    VarInt,
-    VarIntDecodeError
+use crate::varint::VarIntDecodeError;
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
 };
-use core::fmt::{ self, Display, Formatter };
-use std::string::FromUtf8Error;
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{ String, FromUtf8Error }
+};
+
+
+/// The maximum number of characters that the protocol allows in a string, unless a field specifies otherwise.
+///
+/// Characters are counted as UTF-16 code units.
+pub const MAX_STRING_CHARS : usize = 32767;
+
+
+impl<I> DecodeIter<I>
+where
+    I : ExactSizeIterator<Item = u8>
+{
+
+    /// Reads a [`VarInt`](crate::varint::VarInt) length prefixed string with no more than `max_chars` characters.
+    ///
+    /// Characters are counted as UTF-16 code units.
+    /// Lengths which could not possibly fit in `max_chars` characters are rejected before any bytes are allocated.
+    pub fn read_string(&mut self, max_chars : usize) -> Result<String, StringDecodeError> {
+        // Every UTF-16 code unit takes up no more than 3 bytes in UTF-8.
+        let length = decode_prefixed_len(self, max_chars.saturating_mul(3)).map_err(StringDecodeError::Length)?;
+        let bytes  = self.read_vec(length)?;
+        let string = String::from_utf8(bytes).map_err(StringDecodeError::Utf8)?;
+        if (length > max_chars) {
+            let chars = string.encode_utf16().count();
+            if (chars > max_chars) {
+                return Err(StringDecodeError::TooLong { chars, max : max_chars });
+            }
+        }
+        Ok(string)
+    }
+
+    /// Reads `len` bytes as a UTF-8 string, without a [`VarInt`](crate::varint::VarInt) length.
+    pub fn read_unprefixed_string(&mut self, len : usize) -> Result<String, StringDecodeError> {
+        let bytes = self.read_vec(len)?;
+        String::from_utf8(bytes).map_err(StringDecodeError::Utf8)
+    }
+
+}
 
 
 impl PacketDecode for String {
     type Error = StringDecodeError;
 
+    #[inline]
     fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
     where
         I : ExactSizeIterator<Item = u8>
-    {
-        let length = *VarInt::<u32>::decode(iter).map_err(StringDecodeError::Length)? as usize;
-        let bytes  = iter.read_vec(length)?;
-        let string = String::from_utf8(bytes).map_err(StringDecodeError::Utf8)?;
-        Ok(string)
-    }
+    { iter.read_string(MAX_STRING_CHARS) }
+}
+
+
+/// Decodes in the same way as `String`.
+///
+/// The bytes are read into an allocation of exactly the right size, so no reallocation is needed.
+impl PacketDecode for Box<str> {
+    type Error = StringDecodeError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { iter.read_string(MAX_STRING_CHARS).map(String::into_boxed_str) }
+}
+
+
+/// Decodes in the same way as `String`, into [`Cow::Owned`].
+///
+/// A [`DecodeIter`] yields bytes by value, so there is never a received buffer to borrow from.
+/// Packets holding a `Cow<'a, str>` can still be decoded, and encode [`Cow::Borrowed`] without cloning.
+impl PacketDecode for Cow<'_, str> {
+    type Error = StringDecodeError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { iter.read_string(MAX_STRING_CHARS).map(Cow::Owned) }
+}
+
+
+/// A string which will be encoded as UTF-8 without a [`VarInt`](crate::varint::VarInt) length.
+///
+/// Decoding reads all bytes remaining in the packet, so this must be the last field of a packet.
+/// Strings with a length known from context can be read with [`DecodeIter::read_unprefixed_string`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct UnprefixedString(pub String);
+
+impl From<String> for UnprefixedString {
+    #[inline(always)]
+    fn from(value : String) -> Self { Self(value) }
 }
 
+impl Deref for UnprefixedString {
+    type Target = str;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for UnprefixedString {
+    type Error = StringDecodeError;
+    const MIN_ENCODED_LEN : usize = 0;
 
-/// Returned by packet decoders when a `String` was not decoded successfully.
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { iter.read_unprefixed_string(iter.remaining()).map(Self) }
+}
+
+
+/// Returned by packet decoders when a `String`, `Box<str>`, or `Cow<str>` was not decoded successfully.
 #[derive(Debug)]
 pub enum StringDecodeError {
     /// The length of the string failed to decode.
-    Length(VarIntDecodeError),
+    Length(LengthDecodeError),
     /// There were not enough bytes.
     Incomplete(IncompleteDecodeError),
     /// The decoded string was not valid UTF8.
-    Utf8(FromUtf8Error)
+    Utf8(FromUtf8Error),
+    /// The decoded string had more characters than allowed.
+    TooLong {
+        /// The number of characters in the string.
+        chars : usize,
+        /// The maximum number of characters allowed.
+        max   : usize
+    }
 }
 impl From<IncompleteDecodeError> for StringDecodeError {
     #[inline(always)]
     fn from(err : IncompleteDecodeError) -> Self { Self::Incomplete(err) }
 }
+impl From<LengthDecodeError> for StringDecodeError {
+    #[inline(always)]
+    fn from(err : LengthDecodeError) -> Self { Self::Length(err) }
+}
+impl From<VarIntDecodeError> for StringDecodeError {
+    #[inline(always)]
+    fn from(err : VarIntDecodeError) -> Self { Self::Length(LengthDecodeError::VarInt(err)) }
+}
 impl Display for StringDecodeError {
     fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
-        Self::Length(err)     => write!(f, "length {err}"),
-        Self::Incomplete(err) => err.fmt(f),
-        Self::Utf8(_)         => write!(f, "invalid utf8")
+        Self::Length(err)            => write!(f, "length {err}"),
+        Self::Incomplete(err)        => err.fmt(f),
+        Self::Utf8(_)                => write!(f, "invalid utf8"),
+        Self::TooLong { chars, max } => write!(f, "too long ({chars} > {max} chars)")
     } }
 }
+impl core::error::Error for StringDecodeError { }
+/// Converts into an [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) error if there were not enough bytes,
+///  or an [`InvalidData`](std::io::ErrorKind::InvalidData) error otherwise.
+#[cfg(feature = "std")]
+impl From<StringDecodeError> for std::io::Error {
+    fn from(err : StringDecodeError) -> Self {
+        let kind = match (err) {
+            StringDecodeError::Incomplete(_)
+            | StringDecodeError::Length(LengthDecodeError::VarInt(VarIntDecodeError::Incomplete(_))) => std::io::ErrorKind::UnexpectedEof,
+            _ => std::io::ErrorKind::InvalidData
+        };
+        Self::new(kind, err)
+    }
+}
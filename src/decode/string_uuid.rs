@@ -0,0 +1,72 @@
+//! `StringUuid` decoder.
+
+
+use crate::decode::{
+    DecodeIter,
+    PacketDecode,
+    IncompleteDecodeError,
+    string::StringDecodeError
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
+};
+use uuid::{
+    Uuid,
+    fmt::Hyphenated
+};
+use alloc::string::String;
+
+
+/// A [`Uuid`] which is sent as a length prefixed, hyphenated string, such as `"069a79f4-44e9-4726-a5be-fca90e38aaf5"`.
+///
+/// A plain [`Uuid`] is sent as a 128-bit integer instead.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct StringUuid(pub Uuid);
+
+impl From<Uuid> for StringUuid {
+    #[inline(always)]
+    fn from(value : Uuid) -> Self { Self(value) }
+}
+
+impl Deref for StringUuid {
+    type Target = Uuid;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for StringUuid {
+    type Error = StringUuidError;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let string = iter.read_string(Hyphenated::LENGTH).map_err(StringUuidError::String)?;
+        // `Uuid::try_parse` also accepts the simple, braced, and URN forms, none of which are this long.
+        if (string.len() != Hyphenated::LENGTH) {
+            return Err(StringUuidError::Parse(string));
+        }
+        Uuid::try_parse(&string).map(Self).map_err(|_| StringUuidError::Parse(string))
+    }
+}
+
+
+/// Returned by packet decoders when a `StringUuid` was not decoded successfully.
+#[derive(Debug)]
+pub enum StringUuidError {
+    /// The string failed to decode.
+    String(StringDecodeError),
+    /// The string was not a hyphenated UUID.
+    Parse(String)
+}
+impl From<IncompleteDecodeError> for StringUuidError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::String(StringDecodeError::Incomplete(err)) }
+}
+impl Display for StringUuidError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::String(err)   => err.fmt(f),
+        Self::Parse(string) => write!(f, "invalid uuid {string:?}")
+    } }
+}
@@ -0,0 +1,97 @@
+//! `Tristate` decoder.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::fmt::{ self, Display, Formatter };
+
+
+/// A setting which is unset, true, or false.
+///
+/// Encoded as a single byte: `0` for [`Unset`](Self::Unset), `1` for [`True`](Self::True), and `2` for [`False`](Self::False).
+/// As all three values are below `0x80`, this is also the same as a [`VarInt`](crate::varint::VarInt) of `0`, `1`, or `2`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Tristate {
+    /// The setting is not set, and the default should be used.
+    #[default]
+    Unset,
+    /// The setting is set, and is `true`.
+    True,
+    /// The setting is set, and is `false`.
+    False
+}
+
+impl Tristate {
+
+    /// Returns the byte that this is encoded as.
+    #[inline(always)]
+    pub const fn to_byte(self) -> u8 { match (self) {
+        Self::Unset => 0,
+        Self::True  => 1,
+        Self::False => 2
+    } }
+
+    /// Returns the state for an encoded byte, or `None` if `byte` is greater than `2`.
+    #[inline(always)]
+    pub const fn from_byte(byte : u8) -> Option<Self> { match (byte) {
+        0 => Some(Self::Unset),
+        1 => Some(Self::True),
+        2 => Some(Self::False),
+        _ => None
+    } }
+
+}
+
+impl From<Option<bool>> for Tristate {
+    #[inline(always)]
+    fn from(value : Option<bool>) -> Self { match (value) {
+        None        => Self::Unset,
+        Some(true)  => Self::True,
+        Some(false) => Self::False
+    } }
+}
+
+impl From<Tristate> for Option<bool> {
+    #[inline(always)]
+    fn from(value : Tristate) -> Self { match (value) {
+        Tristate::Unset => None,
+        Tristate::True  => Some(true),
+        Tristate::False => Some(false)
+    } }
+}
+
+impl PacketDecode for Tristate {
+    type Error = TristateError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let byte = iter.read()?;
+        Self::from_byte(byte).ok_or(TristateError::Invalid(byte))
+    }
+}
+
+
+/// Returned by packet decoders when a `Tristate` was not decoded successfully.
+#[derive(Debug)]
+pub enum TristateError {
+    /// There were not enough bytes.
+    Incomplete(IncompleteDecodeError),
+    /// The byte was greater than `2`.
+    Invalid(u8)
+}
+impl From<IncompleteDecodeError> for TristateError {
+    #[inline(always)]
+    fn from(err : IncompleteDecodeError) -> Self { Self::Incomplete(err) }
+}
+impl Display for TristateError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Incomplete(err) => err.fmt(f),
+        Self::Invalid(byte)   => write!(f, "invalid tristate {byte}")
+    } }
+}
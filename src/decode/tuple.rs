@@ -0,0 +1,74 @@
+//! Tuple decoders.
+//!
+//! Tuples are decoded as each of their items in order, with no length or other prefix.
+
+
+use crate::decode::{
+    PacketDecode,
+    DecodeIter,
+    IncompleteDecodeError
+};
+use core::fmt::{ self, Display, Formatter };
+
+
+impl PacketDecode for () {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = 0;
+
+    #[inline(always)]
+    fn decode<I>(_iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(()) }
+}
+
+
+macro impl_packetdecode_for_tuple($error:ident, $len:literal; $( $generics:ident $index:tt ),* $(,)?) {
+
+    impl< $( $generics , )* > PacketDecode for ( $( $generics , )* )
+    where
+        $( $generics : PacketDecode , )*
+    {
+        type Error = $error< $( $generics::Error , )* >;
+        const MIN_ENCODED_LEN : usize = 0 $( + $generics::MIN_ENCODED_LEN )*;
+
+        fn decode<Iter>(iter : &mut DecodeIter<Iter>) -> Result<Self, Self::Error>
+        where
+            Iter : ExactSizeIterator<Item = u8>
+        { Ok(( $( $generics::decode(iter).map_err($error::$generics)? , )* )) }
+    }
+
+    #[doc = concat!("Returned by packet decoders when a tuple of ", stringify!($len), " items was not decoded successfully.")]
+    #[derive(Debug)]
+    pub enum $error< $( $generics , )* > { $(
+        #[doc = concat!("Item ", stringify!($index), " failed to decode.")]
+        $generics($generics)
+    , )* }
+
+    impl< $( $generics , )* > Display for $error< $( $generics , )* >
+    where
+        $( $generics : Display , )*
+    {
+        fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) { $(
+            Self::$generics(err) => write!(f, "item {} {err}", $index)
+        , )* } }
+    }
+
+}
+
+impl_packetdecode_for_tuple!(Tuple1DecodeError, 1; A 0);
+impl_packetdecode_for_tuple!(Tuple2DecodeError, 2; A 0, B 1);
+impl_packetdecode_for_tuple!(Tuple3DecodeError, 3; A 0, B 1, C 2);
+impl_packetdecode_for_tuple!(Tuple4DecodeError, 4; A 0, B 1, C 2, D 3);
+impl_packetdecode_for_tuple!(Tuple5DecodeError, 5; A 0, B 1, C 2, D 3, E 4);
+impl_packetdecode_for_tuple!(Tuple6DecodeError, 6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_packetdecode_for_tuple!(Tuple7DecodeError, 7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_packetdecode_for_tuple!(Tuple8DecodeError, 8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_packetdecode_for_tuple!(Tuple9DecodeError, 9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_packetdecode_for_tuple!(Tuple10DecodeError, 10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_packetdecode_for_tuple!(Tuple11DecodeError, 11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_packetdecode_for_tuple!(Tuple12DecodeError, 12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_packetdecode_for_tuple!(Tuple13DecodeError, 13; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_packetdecode_for_tuple!(Tuple14DecodeError, 14; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_packetdecode_for_tuple!(Tuple15DecodeError, 15; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_packetdecode_for_tuple!(Tuple16DecodeError, 16; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
@@ -0,0 +1,120 @@
+//! `UuidMostLeast` and `UuidIntArray` decoders.
+
+
+use crate::decode::{
+    DecodeIter,
+    PacketDecode,
+    IncompleteDecodeError
+};
+use core::ops::Deref;
+use uuid::Uuid;
+
+
+/// A [`Uuid`] which is sent as two `i64`s: the most significant bits, followed by the least significant bits.
+///
+/// This is the layout of `UUIDMost` and `UUIDLeast` in older NBT.
+/// As both halves are big-endian, the bytes are the same as a plain [`Uuid`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct UuidMostLeast(pub Uuid);
+
+impl UuidMostLeast {
+
+    /// Returns the most and least significant bits of the UUID.
+    #[inline]
+    pub fn to_most_least(self) -> (i64, i64,) {
+        let (most, least,) = self.0.as_u64_pair();
+        (most.cast_signed(), least.cast_signed(),)
+    }
+
+    /// Creates a UUID from its most and least significant bits.
+    #[inline]
+    pub fn from_most_least(most : i64, least : i64) -> Self {
+        Self(Uuid::from_u64_pair(most.cast_unsigned(), least.cast_unsigned()))
+    }
+
+}
+
+impl From<Uuid> for UuidMostLeast {
+    #[inline(always)]
+    fn from(value : Uuid) -> Self { Self(value) }
+}
+
+impl Deref for UuidMostLeast {
+    type Target = Uuid;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for UuidMostLeast {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<u128>();
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let most  = i64::decode(iter)?;
+        let least = i64::decode(iter)?;
+        Ok(Self::from_most_least(most, least))
+    }
+}
+
+
+/// A [`Uuid`] which is sent as four `i32`s, from most to least significant.
+///
+/// This is the layout of an `IntArray` UUID in NBT.
+/// As every part is big-endian, the bytes are the same as a plain [`Uuid`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct UuidIntArray(pub Uuid);
+
+impl UuidIntArray {
+
+    /// Returns the four parts of the UUID, from most to least significant.
+    #[inline]
+    pub fn to_ints(self) -> [i32; 4] {
+        let bits = self.0.as_u128();
+        [
+            (bits >> 96) as u32 as i32,
+            (bits >> 64) as u32 as i32,
+            (bits >> 32) as u32 as i32,
+            bits as u32 as i32
+        ]
+    }
+
+    /// Creates a UUID from its four parts, from most to least significant.
+    #[inline]
+    pub fn from_ints(ints : [i32; 4]) -> Self {
+        let bits = ints.into_iter().fold(0u128, |bits, int| (bits << 32) | (int.cast_unsigned() as u128));
+        Self(Uuid::from_u128(bits))
+    }
+
+}
+
+impl From<Uuid> for UuidIntArray {
+    #[inline(always)]
+    fn from(value : Uuid) -> Self { Self(value) }
+}
+
+impl Deref for UuidIntArray {
+    type Target = Uuid;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl PacketDecode for UuidIntArray {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<u128>();
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let mut ints = [0i32; 4];
+        for int in &mut ints {
+            *int = i32::decode(iter)?;
+        }
+        Ok(Self::from_ints(ints))
+    }
+}
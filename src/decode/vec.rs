@@ -1,17 +1,47 @@
-//! `Vec<T>` decoder.
+//! `Vec<T>` and `Box<[T]>` decoders.
 
 
 use crate::decode::{
     PacketDecode,
-    DecodeIter
-};
-use crate::varint::{
-    VarInt,
-    VarIntDecodeError
+    DecodeIter,
+    DepthLimitExceededError,
+    LengthDecodeError,
+    capacity_hint,
+    decode_collection_len
 };
+use crate::varint::VarIntDecodeError;
 use core::fmt::{ self, Display, Formatter };
+use alloc::{
+    boxed::Box,
+    collections::VecDeque,
+    vec::Vec
+};
 
 
+/// Decodes a [`VarInt::<u32>`](crate::varint::VarInt) length, followed by each item.
+///
+/// No more space is reserved than the remaining bytes could hold, so a huge length in a short packet fails without allocating for it.
+/// Items which can be decoded from no bytes, such as [`UnprefixedString`](crate::decode::string::UnprefixedString),
+///  are rejected with [`LengthDecodeError::TooLong`] if there are more of them than remaining bytes.
+///
+/// ```
+/// use pipeworkmc_codec::decode::{
+///     PacketDecode, DecodeIter, LengthDecodeError,
+///     string::UnprefixedString,
+///     vec::VecDecodeError
+/// };
+///
+/// // A length of `i32::MAX`, with nothing after it.
+/// let huge = [0xFF, 0xFF, 0xFF, 0xFF, 0x07];
+/// assert!(matches!(
+///     Vec::<u64>::decode(&mut DecodeIter::from(huge.into_iter())),
+///     Err(VecDecodeError::Item { index : 0, .. })
+/// ));
+/// assert!(matches!(
+///     Vec::<UnprefixedString>::decode(&mut DecodeIter::from(huge.into_iter())),
+///     Err(VecDecodeError::Length(LengthDecodeError::TooLong { max : 0, .. }))
+/// ));
+/// ```
 impl<T> PacketDecode for Vec<T>
 where
     T : PacketDecode
@@ -22,21 +52,57 @@ where
     where
         I : ExactSizeIterator<Item = u8>
     {
-        let     length = *VarInt::<u32>::decode(iter).map_err(VecDecodeError::Length)? as usize;
-        let mut vec    = Vec::with_capacity(length);
-        for i in 0..length {
-            vec.push(T::decode(iter).map_err(|err| VecDecodeError::Item { index : i, err })?);
-        }
-        Ok(vec)
+        iter.nested(|iter| {
+            let     length = decode_collection_len::<T, _>(iter).map_err(VecDecodeError::Length)?;
+            let mut vec    = Vec::with_capacity(capacity_hint::<T, _>(iter, length));
+            for i in 0..length {
+                vec.push(T::decode(iter).map_err(|err| VecDecodeError::Item { index : i, err })?);
+            }
+            Ok(vec)
+        })
+    }
+}
+
+
+impl<T> PacketDecode for Box<[T]>
+where
+    T : PacketDecode
+{
+    type Error = VecDecodeError<T::Error>;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        // The capacity is only smaller than the length if the packet is too short, in which case decoding fails anyway.
+        Ok(Vec::<T>::decode(iter)?.into_boxed_slice())
     }
 }
 
 
-/// Returned by packet decoders when a `Vec<T>` was not decoded successfully.
+/// Decodes in the same way as `Vec<T>`, from front to back.
+impl<T> PacketDecode for VecDeque<T>
+where
+    T : PacketDecode
+{
+    type Error = VecDecodeError<T::Error>;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(VecDeque::from(Vec::<T>::decode(iter)?)) }
+}
+
+
+/// Returned by packet decoders when a `Vec<T>`, `Box<[T]>`, or `VecDeque<T>` was not decoded successfully.
 #[derive(Debug)]
 pub enum VecDecodeError<E> {
     /// The length of the vector failed to decode.
-    Length(VarIntDecodeError),
+    Length(LengthDecodeError),
+    /// Collections were nested too deeply.
+    Depth(DepthLimitExceededError),
     /// An item in the vector could not be decoded.
     Item {
         /// The index of the item that was not decoded.
@@ -45,12 +111,25 @@ pub enum VecDecodeError<E> {
         err   : E
     }
 }
+impl<E> From<DepthLimitExceededError> for VecDecodeError<E> {
+    #[inline(always)]
+    fn from(err : DepthLimitExceededError) -> Self { Self::Depth(err) }
+}
+impl<E> From<LengthDecodeError> for VecDecodeError<E> {
+    #[inline(always)]
+    fn from(err : LengthDecodeError) -> Self { Self::Length(err) }
+}
+impl<E> From<VarIntDecodeError> for VecDecodeError<E> {
+    #[inline(always)]
+    fn from(err : VarIntDecodeError) -> Self { Self::Length(LengthDecodeError::VarInt(err)) }
+}
 impl<E> Display for VecDecodeError<E>
 where
     E : Display
 {
     fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
         Self::Length(err)          => write!(f, "length {err}"),
+        Self::Depth(err)           => err.fmt(f),
         Self::Item { index, err } => write!(f, "item {index} {err}")
     } }
 }
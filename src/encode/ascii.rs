@@ -0,0 +1,35 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::ascii::{ FixedAscii, AsciiChar };
+
+
+unsafe impl<const N : usize> PacketEncode for FixedAscii<N> {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { N }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        let bytes = &self.as_bytes()[..self.len().min(N)];
+        buf.write_slice(bytes);
+        for _ in bytes.len()..N {
+            buf.write(0);
+        }
+    } }
+
+}
+
+
+unsafe impl PacketEncode for AsciiChar {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(1);
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { 1 }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write(self.to_byte())
+    } }
+
+}
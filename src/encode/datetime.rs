@@ -2,16 +2,52 @@ use crate::encode::{
     PacketEncode,
     EncodeBuf
 };
-use chrono::{ DateTime, Utc };
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    TimeDelta,
+    Utc
+};
 
 
 unsafe impl PacketEncode for DateTime<Utc> {
+
+    #[inline]
     fn encode_len(&self) -> usize {
         self.timestamp().encode_len()
         + self.timestamp_subsec_nanos().encode_len()
     }
+
     unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
         self.timestamp().encode(buf);
         self.timestamp_subsec_nanos().encode(buf);
     } }
+
+}
+
+
+unsafe impl PacketEncode for NaiveDateTime {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.and_utc().encode_len() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.and_utc().encode(buf)
+    } }
+
+}
+
+
+/// Encoded as an `i64` number of milliseconds, truncating any sub-millisecond precision.
+unsafe impl PacketEncode for TimeDelta {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.num_milliseconds().encode_len() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.num_milliseconds().encode(buf)
+    } }
+
 }
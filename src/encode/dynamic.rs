@@ -0,0 +1,34 @@
+//! The dyn-compatible `DynPacketEncode` trait.
+
+
+use crate::encode::PacketEncode;
+use alloc::vec::Vec;
+
+
+/// A dyn-compatible version of [`PacketEncode`], which only exposes safe methods.
+///
+/// This is implemented for all types implementing [`PacketEncode`],
+///  and can be used to hold different packet types in a `Box<dyn DynPacketEncode>`.
+/// Its methods are prefixed with `dyn_`, so that they do not make calls on types which also implement [`PacketEncode`] ambiguous.
+pub trait DynPacketEncode {
+
+    /// Returns the exact number of bytes that `self.dyn_encode_to_vec()` will return.
+    fn dyn_encode_len(&self) -> usize;
+
+    /// Encode this value into a new vector of exactly `self.dyn_encode_len()` bytes.
+    fn dyn_encode_to_vec(&self) -> Vec<u8>;
+
+}
+
+impl<T> DynPacketEncode for T
+where
+    T : PacketEncode + ?Sized
+{
+
+    #[inline(always)]
+    fn dyn_encode_len(&self) -> usize { <T as PacketEncode>::encode_len(self) }
+
+    #[inline(always)]
+    fn dyn_encode_to_vec(&self) -> Vec<u8> { <T as PacketEncode>::encode_to_vec(self) }
+
+}
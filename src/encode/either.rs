@@ -0,0 +1,32 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::either::TaggedEither;
+
+
+unsafe impl<L, R> PacketEncode for TaggedEither<L, R>
+where
+    L : PacketEncode,
+    R : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize { match (self) {
+        Self::Left(left)   => true.encode_len() + left.encode_len(),
+        Self::Right(right) => false.encode_len() + right.encode_len()
+    } }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe { match (self) {
+        Self::Left(left) => {
+            true.encode(buf);
+            left.encode(buf);
+        },
+        Self::Right(right) => {
+            false.encode(buf);
+            right.encode(buf);
+        }
+    } } }
+
+}
@@ -0,0 +1,19 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::elapsed::ElapsedMillis;
+use crate::varint::VarInt;
+
+
+unsafe impl PacketEncode for ElapsedMillis {
+
+    #[inline]
+    fn encode_len(&self) -> usize { VarInt::<u64>(self.to_millis()).encode_len() }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u64>(self.to_millis()).encode(buf)
+    } }
+
+}
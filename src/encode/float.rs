@@ -0,0 +1,27 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::float::{
+    CanonicalF32,
+    CanonicalF64
+};
+
+
+macro impl_packetencode_for_canonical_float($ty:ty => $inner:ty) {
+    unsafe impl PacketEncode for $ty {
+        const FIXED_ENCODE_LEN : Option<usize> = <$inner as PacketEncode>::FIXED_ENCODE_LEN;
+
+        #[inline(always)]
+        fn encode_len(&self) -> usize { self.0.encode_len() }
+
+        #[inline]
+        unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+            self.get().encode(buf)
+        } }
+
+    }
+}
+
+impl_packetencode_for_canonical_float!(CanonicalF32 => f32);
+impl_packetencode_for_canonical_float!(CanonicalF64 => f64);
@@ -0,0 +1,32 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::le::Le;
+
+
+macro impl_packetencode_for_le($ty:ty) {
+    unsafe impl PacketEncode for Le<$ty> {
+        const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<$ty>());
+
+        #[inline(always)]
+        fn encode_len(&self) -> usize { size_of::<$ty>() }
+
+        #[inline]
+        unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+            buf.write_slice(&self.0.to_le_bytes())
+        } }
+
+    }
+}
+
+impl_packetencode_for_le!(u16);
+impl_packetencode_for_le!(i16);
+impl_packetencode_for_le!(u32);
+impl_packetencode_for_le!(i32);
+impl_packetencode_for_le!(u64);
+impl_packetencode_for_le!(i64);
+impl_packetencode_for_le!(u128);
+impl_packetencode_for_le!(i128);
+impl_packetencode_for_le!(f32);
+impl_packetencode_for_le!(f64);
@@ -0,0 +1,141 @@
+//! `HashMap<K, V>` and `SortedMap<K, V>` encoders.
+
+
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::varint::VarInt;
+use core::{
+    hash::BuildHasher,
+    ops::Deref
+};
+use alloc::vec::Vec;
+use std::{
+    collections::HashMap,
+    hash::RandomState
+};
+
+
+/// Encoded as a [`VarInt::<u32>`](VarInt) length, followed by each key and its value, in the map's iteration order.
+///
+/// Use [`SortedMap`] if two maps with the same entries must encode to the same bytes.
+///
+/// The length of a map is only computed without visiting every entry if both `K` and `V` have a [`FIXED_ENCODE_LEN`](PacketEncode::FIXED_ENCODE_LEN).
+/// [`VarInt`] has a variable length, so maps of `VarInt`s, such as palettes, compute the length of every entry.
+unsafe impl<K, V, S> PacketEncode for HashMap<K, V, S>
+where
+    K : PacketEncode,
+    V : PacketEncode,
+    S : BuildHasher
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt::<u32>(self.len() as u32).encode_len().saturating_add(entries_encode_len(self))
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.len() as u32).encode(buf);
+        for (key, value,) in self {
+            key.encode(buf);
+            value.encode(buf);
+        }
+    } }
+
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        VarInt::<u32>(self.len() as u32).encode_to_writer(writer)?;
+        for (key, value,) in self {
+            key.encode_to_writer(writer)?;
+            value.encode_to_writer(writer)?;
+        }
+        Ok(())
+    }
+
+}
+
+
+/// Returns the total number of bytes that the entries in `map` will write, without visiting every entry if they have a fixed length.
+#[inline(always)]
+fn entries_encode_len<K, V, S>(map : &HashMap<K, V, S>) -> usize
+where
+    K : PacketEncode,
+    V : PacketEncode
+{
+    match (K::FIXED_ENCODE_LEN, V::FIXED_ENCODE_LEN,) {
+        (Some(key_len), Some(value_len),) => map.len().saturating_mul(key_len.saturating_add(value_len)),
+        _                                 => map.iter().fold(0, |len, (key, value,)| len.saturating_add(key.encode_len()).saturating_add(value.encode_len()))
+    }
+}
+
+
+/// A `HashMap<K, V>` which is always encoded with its entries in ascending key order.
+///
+/// The iteration order of a `HashMap` depends on its hasher, so two maps with the same entries can otherwise encode differently.
+/// This matters for payloads which are hashed or signed.
+///
+/// Encoded as a [`VarInt::<u32>`](VarInt) length, followed by each key and its value.
+#[derive(Clone, Default, Debug)]
+pub struct SortedMap<K, V, S = RandomState>(pub HashMap<K, V, S>);
+
+impl<K, V, S> From<HashMap<K, V, S>> for SortedMap<K, V, S> {
+    #[inline(always)]
+    fn from(value : HashMap<K, V, S>) -> Self { Self(value) }
+}
+
+impl<K, V, S> Deref for SortedMap<K, V, S> {
+    type Target = HashMap<K, V, S>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<K, V, S> SortedMap<K, V, S>
+where
+    K : Ord
+{
+    /// Returns the entries of the map in ascending key order.
+    fn sorted_entries(&self) -> Vec<(&K, &V,)> {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|&(key, _,)| key);
+        entries
+    }
+}
+
+unsafe impl<K, V, S> PacketEncode for SortedMap<K, V, S>
+where
+    K : PacketEncode + Ord,
+    V : PacketEncode,
+    S : BuildHasher
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt::<u32>(self.0.len() as u32).encode_len().saturating_add(entries_encode_len(&self.0))
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.0.len() as u32).encode(buf);
+        for (key, value,) in self.sorted_entries() {
+            key.encode(buf);
+            value.encode(buf);
+        }
+    } }
+
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        VarInt::<u32>(self.0.len() as u32).encode_to_writer(writer)?;
+        for (key, value,) in self.sorted_entries() {
+            key.encode_to_writer(writer)?;
+            value.encode_to_writer(writer)?;
+        }
+        Ok(())
+    }
+
+}
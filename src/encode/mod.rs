@@ -9,14 +9,36 @@ use core::{
     mem::{ self, MaybeUninit },
     ptr
 };
+use alloc::{
+    boxed::Box,
+    vec::Vec
+};
 
 
+mod ascii;
+pub mod dynamic;
+mod either;
+mod elapsed;
+mod float;
+mod le;
+#[cfg(feature = "std")]
+pub mod map;
 mod num;
 mod refs;
 mod option;
+mod optional;
+mod packed_bools;
+pub mod pre_encoded;
+mod range;
+mod rest;
+mod result;
+mod set;
 pub mod slice;
 mod str;
+mod string_uuid;
+mod tristate;
 mod tuple;
+mod uuid_parts;
 #[cfg(feature = "chrono")]
 mod datetime;
 
@@ -53,6 +75,10 @@ impl EncodeBuf {
     #[inline(always)]
     pub fn len(&self) -> usize { self.buf.len() }
 
+    /// Returns `true` if this buffer has a total length of 0.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.buf.is_empty() }
+
     /// Returns the inner buffer.
     ///
     /// ### Safety
@@ -83,6 +109,20 @@ impl EncodeBuf {
         Self { head : 0, buf : Box::new_uninit_slice(len) }
     }
 
+    /// Creates a new empty [`EncodeBuf`] which reuses the allocation of `vec`.
+    ///
+    /// The total length of the buffer will be `vec.capacity()`, and any existing contents are discarded.
+    /// [`EncodeBuf::into_inner_as_vec`] returns the same allocation, so buffers can be recycled without reallocating.
+    pub fn from_vec(vec : Vec<u8>) -> Self {
+        let mut vec = mem::ManuallyDrop::new(vec);
+        let     cap = vec.capacity();
+        // SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and uninitialised bytes are valid for all `cap` items.
+        //         `vec` is not dropped, so the allocation is now owned by the new vector.
+        let vec = unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast::<MaybeUninit<u8>>(), cap, cap) };
+        // `into_boxed_slice` does not reallocate, because the length is equal to the capacity.
+        Self { head : 0, buf : vec.into_boxed_slice() }
+    }
+
     /// Creates a new empty [`EncodeBuf`] with enough space allocated to write `len` more bytes.
     /// The size will be written at the start of the packet as a [`VarInt::<u32>`](VarInt).
     ///
@@ -90,7 +130,7 @@ impl EncodeBuf {
     #[inline]
     pub fn new_len_prefixed(len : usize) -> Self {
         let len_varint = VarInt::<u32>(len as u32);
-        let mut buf = Self::new(len_varint.encode_len() + len);
+        let mut buf = Self::new(len_varint.encode_len().saturating_add(len));
         unsafe { len_varint.encode(&mut buf); }
         buf
     }
@@ -100,7 +140,9 @@ impl EncodeBuf {
     /// ### Safety
     /// The caller is responsible for ensuring that this buffer has enough space to write this byte.
     /// Writing more than `self.len()` total bytes is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html).
+    /// In debug builds, this panics instead.
     pub unsafe fn write(&mut self, b : u8) {
+        debug_assert!(self.head < self.buf.len(), "`EncodeBuf` overflowed (wrote 1 byte at {} of {})", self.head, self.buf.len());
         unsafe { self.buf.get_unchecked_mut(self.head) }.write(b);
         self.head += 1;
     }
@@ -110,7 +152,9 @@ impl EncodeBuf {
     /// ### Safety
     /// The caller is responsible for ensuring that this buffer has enough space to write these bytes.
     /// Writing more than `self.len()` total bytes is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html).
+    /// In debug builds, this panics instead.
     pub unsafe fn write_slice(&mut self, slice : &[u8]) {
+        debug_assert!(self.head + slice.len() <= self.buf.len(), "`EncodeBuf` overflowed (wrote {} bytes at {} of {})", slice.len(), self.head, self.buf.len());
         unsafe { ptr::copy_nonoverlapping(
             slice.as_ptr(),
             mem::transmute::<&mut [MaybeUninit<u8>], &mut [u8]>(
@@ -121,16 +165,121 @@ impl EncodeBuf {
         self.head += slice.len();
     }
 
+    /// Returns the number of bytes that [`EncodeBuf::write_len_prefixed_slice`] will write for a slice of `len` bytes.
+    #[inline]
+    pub fn len_prefixed_slice_len(len : usize) -> usize {
+        VarInt::<u32>(len as u32).encode_len().saturating_add(len)
+    }
+
+    /// Writes the length of a slice of bytes as a [`VarInt::<u32>`](VarInt), followed by the bytes, to this buffer.
+    ///
+    /// ### Safety
+    /// The caller is responsible for ensuring that this buffer has enough space to write [`EncodeBuf::len_prefixed_slice_len`] bytes.
+    /// Writing more than `self.len()` total bytes is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html).
+    pub unsafe fn write_len_prefixed_slice(&mut self, slice : &[u8]) { unsafe {
+        VarInt::<u32>(slice.len() as u32).encode(self);
+        self.write_slice(slice);
+    } }
+
+    /// Returns the bytes written to this buffer since `offset`.
+    ///
+    /// ### Panics
+    /// Panics if `offset` is greater than [`EncodeBuf::written`].
+    #[inline]
+    pub fn written_slice_from(&self, offset : usize) -> &[u8] {
+        &self.as_slice()[offset..]
+    }
+
+    /// Reserves space for a [`VarInt::<u32>`](VarInt) which will be written later with [`EncodeBuf::patch_varint`].
+    ///
+    /// This writes [`PatchHandle::LEN`] placeholder bytes.
+    /// The patched VarInt is padded to the same width, so it can be used for a length which is not known until after the following bytes are encoded.
+    ///
+    /// ### Safety
+    /// The caller is responsible for ensuring that this buffer has enough space to write [`PatchHandle::LEN`] bytes.
+    /// Writing more than `self.len()` total bytes is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html).
+    /// In debug builds, this panics instead.
+    pub unsafe fn reserve_varint(&mut self) -> PatchHandle {
+        let offset = self.head;
+        unsafe { self.write_slice(&[0; PatchHandle::LEN]); }
+        PatchHandle { offset }
+    }
+
+    /// Writes `value` into the space reserved by [`EncodeBuf::reserve_varint`], as a [`VarInt::<u32>`](VarInt) padded to [`PatchHandle::LEN`] bytes.
+    ///
+    /// ### Panics
+    /// Panics if `handle` does not point to reserved bytes which have been written to this buffer.
+    pub fn patch_varint(&mut self, handle : PatchHandle, value : u32) {
+        assert!(handle.end() <= self.head, "`PatchHandle` at {} is outside of the written bytes of this `EncodeBuf`", handle.offset);
+        let mut value = value;
+        for i in 0..PatchHandle::LEN {
+            let byte = if (i < (PatchHandle::LEN - 1)) {
+                ((value & 0b01111111) as u8) | 0b10000000
+            } else { value as u8 };
+            value >>= 7;
+            self.buf[handle.offset + i].write(byte);
+        }
+    }
+
+}
+
+
+/// Space in an [`EncodeBuf`] which was reserved by [`EncodeBuf::reserve_varint`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PatchHandle {
+    offset : usize
+}
+
+impl PatchHandle {
+
+    /// The number of bytes reserved for a patched [`VarInt::<u32>`](VarInt).
+    pub const LEN : usize = 5;
+
+    /// Returns the offset of the reserved bytes in the buffer.
+    #[inline(always)]
+    pub fn offset(&self) -> usize { self.offset }
+
+    /// Returns the offset just after the reserved bytes, where the following data starts.
+    #[inline(always)]
+    pub fn end(&self) -> usize { self.offset + Self::LEN }
+
 }
 
 
 /// A data structure which can be encoded into bytes.
 ///
+/// Encoding always takes two passes over a value: one through `encode_len` to size the buffer, and one through `encode` to fill it.
+/// Nested collections such as `Vec<Vec<T>>` are therefore walked twice, but each item's length is only computed once per encode.
+/// Callers which need both the length and the bytes should use [`PacketEncode::encode_with_len`], rather than calling `encode_len` again.
+///
 /// ### Safety
 /// The implementor is responsible for ensuring that `encode_len` returns the exact number of bytes that `encode` will write.
 /// Returning an incorrect value is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html), as it will cause `encode` to write too few or too many bytes to the buffer.
+/// The same applies to [`PacketEncode::FIXED_ENCODE_LEN`], which must be `None` unless every value has that length.
 pub unsafe trait PacketEncode {
 
+    /// The number of bytes that `encode` writes, if it is the same for every value of this type.
+    ///
+    /// This lets slices of fixed-length items, such as `Vec<u8>` and `Box<[u8]>`, compute their length without visiting every item.
+    const FIXED_ENCODE_LEN : Option<usize> = None;
+
+    /// Returns `items` as bytes, if each item is encoded as exactly its own single byte.
+    ///
+    /// This lets collections of bytes, such as `Vec<u8>` and `Cow<[u8]>`, be written in a single copy.
+    /// It is an implementation detail, and should not be overridden outside of this crate.
+    ///
+    /// ### Safety
+    /// If this returns `Some`, the bytes must be exactly what encoding each item in turn would write.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn items_as_bytes(items : &[Self]) -> Option<&[u8]>
+    where
+        Self : Sized
+    {
+        let _ = items;
+        None
+    }
+
     /// Returns the exact number of bytes that `self.encode()` will write.
     fn encode_len(&self) -> usize;
 
@@ -141,6 +290,46 @@ pub unsafe trait PacketEncode {
     /// Passing an incorrectly sized buffer is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html), as too few or too many bytes will be written to the buffer.
     unsafe fn encode(&self, buf : &mut EncodeBuf);
 
+    /// Encode this value into a new, completely filled buffer of exactly `self.encode_len()` bytes.
+    ///
+    /// Returns the length alongside the buffer, so that `self.encode_len()` is only computed once.
+    ///
+    /// ### Panics
+    /// In debug builds, panics if `self.encode()` did not write exactly `self.encode_len()` bytes.
+    fn encode_with_len(&self) -> (usize, EncodeBuf,) {
+        let     len = self.encode_len();
+        let mut buf = EncodeBuf::new(len);
+        // SAFETY: `buf` has exactly `self.encode_len()` total bytes of space.
+        unsafe { self.encode(&mut buf); }
+        debug_assert_eq!(buf.written(), len, "`encode` wrote a different number of bytes than `encode_len` returned");
+        (len, buf,)
+    }
+
+    /// Encode this value into a new vector of exactly `self.encode_len()` bytes.
+    ///
+    /// ### Panics
+    /// In debug builds, panics if `self.encode()` did not write exactly `self.encode_len()` bytes.
+    #[inline]
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let (_len, buf,) = self.encode_with_len();
+        // SAFETY: `buf` was completely filled by `self.encode()`.
+        unsafe { buf.into_inner_as_vec() }
+    }
+
+    /// Encode this value into a writer.
+    ///
+    /// By default, this encodes the whole value into a buffer with [`encode_to_vec`](PacketEncode::encode_to_vec), and writes it all at once.
+    /// Collections such as `&[T]`, `Vec<T>` and maps, and wrappers such as tuples and `Option<T>`, instead write their parts as they go,
+    ///  so that large packets such as chunk data are never buffered in full.
+    /// Types which do not override this, such as structs written with [`EncodeBuf`] directly, are still buffered in full.
+    /// As many small writes may be made, `writer` should usually be buffered.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { writer.write_all(&self.encode_to_vec()) }
+
 }
 
 
@@ -163,6 +352,20 @@ pub unsafe trait PrefixedPacketEncode {
     /// Passing an incorrectly sized buffer is [*undefined behaviour*](https://doc.rust-lang.org/reference/behavior-considered-undefined.html), as too few or too many bytes will be written to the buffer.
     unsafe fn encode_prefixed(&self, buf : &mut EncodeBuf);
 
+    /// Encode this value into a new vector of exactly `self.encode_prefixed_len()` bytes.
+    ///
+    /// ### Panics
+    /// In debug builds, panics if `self.encode_prefixed()` did not write exactly `self.encode_prefixed_len()` bytes.
+    fn encode_prefixed_to_vec(&self) -> Vec<u8> {
+        let     len = self.encode_prefixed_len();
+        let mut buf = EncodeBuf::new(len);
+        // SAFETY: `buf` has exactly `self.encode_prefixed_len()` total bytes of space.
+        unsafe { self.encode_prefixed(&mut buf); }
+        debug_assert_eq!(buf.written(), len, "`encode_prefixed` wrote a different number of bytes than `encode_prefixed_len` returned");
+        // SAFETY: `buf` was completely filled by `self.encode_prefixed()`.
+        unsafe { buf.into_inner_as_vec() }
+    }
+
 }
 
 unsafe impl<P> PrefixedPacketEncode for P
@@ -170,14 +373,50 @@ where
     P : PacketEncode + PacketMeta
 {
 
+    /// Saturates at [`usize::MAX`] instead of overflowing, so that an enormous packet fails to allocate rather than being given too small a buffer.
     #[inline(always)]
     fn encode_prefixed_len(&self) -> usize {
-        1 + <P as PacketEncode>::encode_len(self)
+        (if (<P as PacketMeta>::VARINT_PREFIX) {
+            VarInt::<u32>(<P as PacketMeta>::PREFIX).encode_len()
+        } else { 1 })
+        .saturating_add(<P as PacketEncode>::encode_len(self))
     }
 
     unsafe fn encode_prefixed(&self, buf : &mut EncodeBuf) { unsafe {
-        buf.write(<P as PacketMeta>::PREFIX);
+        #[cfg(feature = "observer")]
+        let start = buf.written();
+        if (<P as PacketMeta>::VARINT_PREFIX) {
+            VarInt::<u32>(<P as PacketMeta>::PREFIX).encode(buf);
+        } else {
+            const { assert!(<P as PacketMeta>::VARINT_PREFIX || (<P as PacketMeta>::PREFIX <= (u8::MAX as u32)), "`PacketMeta::PREFIX` does not fit in a byte, but `PacketMeta::VARINT_PREFIX` is not set"); }
+            buf.write(<P as PacketMeta>::PREFIX as u8);
+        }
         <P as PacketEncode>::encode(self, buf);
+        #[cfg(feature = "observer")]
+        crate::observer::observe_encoded::<P>(buf.written() - start);
     } }
 
 }
+
+
+/// Encodes several packets back-to-back into a single allocation.
+///
+/// Each packet is prefixed with its length as a [`VarInt::<u32>`](VarInt), as if it had been encoded into an [`EncodeBuf::new_len_prefixed`].
+pub fn encode_all<P>(packets : &[P]) -> Vec<u8>
+where
+    P : PrefixedPacketEncode
+{
+    // Each packet's length is computed once, and reused for both the allocation and the length prefix.
+    let     packet_lens = packets.iter().map(|packet| packet.encode_prefixed_len()).collect::<Vec<_>>();
+    let     len         = packet_lens.iter().fold(0usize, |len, &packet_len| len.saturating_add(EncodeBuf::len_prefixed_slice_len(packet_len)));
+    let mut buf         = EncodeBuf::new(len);
+    for (packet, packet_len,) in packets.iter().zip(packet_lens) {
+        // SAFETY: `buf` was allocated with enough space for the length and contents of every packet.
+        unsafe {
+            VarInt::<u32>(packet_len as u32).encode(&mut buf);
+            packet.encode_prefixed(&mut buf);
+        }
+    }
+    // SAFETY: Every packet was written, filling `buf`.
+    unsafe { buf.into_inner_as_vec() }
+}
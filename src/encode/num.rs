@@ -2,11 +2,22 @@ use crate::encode::{
     PacketEncode,
     EncodeBuf
 };
+use core::{
+    num::Wrapping,
+    slice
+};
 use uuid::Uuid;
 
 
 macro impl_packetencode_for_num($ty:ty) {
     unsafe impl PacketEncode for $ty {
+        const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<Self>());
+
+        #[inline(always)]
+        fn items_as_bytes(items : &[Self]) -> Option<&[u8]> {
+            // SAFETY: Single byte numbers have the same layout as `u8`, and are encoded as their own byte.
+            (size_of::<Self>() == 1).then(|| unsafe { slice::from_raw_parts(items.as_ptr().cast::<u8>(), items.len()) })
+        }
 
         #[inline(always)]
         fn encode_len(&self) -> usize { size_of::<Self>() }
@@ -34,6 +45,7 @@ impl_packetencode_for_num!(f64);
 
 
 unsafe impl PacketEncode for bool {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<u8>());
 
     #[inline(always)]
     fn encode_len(&self) -> usize { size_of::<u8>() }
@@ -47,6 +59,7 @@ unsafe impl PacketEncode for bool {
 
 
 unsafe impl PacketEncode for Uuid {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<u128>());
 
     #[inline(always)]
     fn encode_len(&self) -> usize { size_of::<u128>() }
@@ -57,3 +70,21 @@ unsafe impl PacketEncode for Uuid {
     } }
 
 }
+
+
+/// Encodes the inner value, with no change to its encoding.
+unsafe impl<T> PacketEncode for Wrapping<T>
+where
+    T : PacketEncode
+{
+    const FIXED_ENCODE_LEN : Option<usize> = T::FIXED_ENCODE_LEN;
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.0.encode_len() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.0.encode(buf)
+    } }
+
+}
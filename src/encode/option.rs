@@ -4,6 +4,9 @@ use crate::encode::{
 };
 
 
+/// Encodes a `bool` tag, followed by the value if it is present.
+///
+/// Through the implementations for references, `Option<&T>` and `&Option<T>` are encoded exactly the same as `Option<T>`, without cloning.
 unsafe impl<T> PacketEncode for Option<T>
 where
     T : PacketEncode
@@ -24,4 +27,18 @@ where
         None => { false.encode(buf); }
     } } }
 
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        match (self) {
+            Some(inner) => {
+                true.encode_to_writer(writer)?;
+                inner.encode_to_writer(writer)
+            },
+            None => false.encode_to_writer(writer)
+        }
+    }
+
 }
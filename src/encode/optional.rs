@@ -0,0 +1,29 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::optional::OptionalVarInt;
+use crate::varint::VarInt;
+
+
+unsafe impl<T> PacketEncode for OptionalVarInt<T>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize { match (&self.0) {
+        Some(inner) => VarInt::<u32>(1).encode_len() + inner.encode_len(),
+        None        => VarInt::<u32>(0).encode_len()
+    } }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe { match (&self.0) {
+        Some(inner) => {
+            VarInt::<u32>(1).encode(buf);
+            inner.encode(buf);
+        },
+        None => { VarInt::<u32>(0).encode(buf); }
+    } } }
+
+}
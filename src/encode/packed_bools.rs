@@ -0,0 +1,23 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::packed_bools::PackedBools;
+
+
+unsafe impl<const N : usize> PacketEncode for PackedBools<N> {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { Self::BYTES }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        for chunk in self.0.chunks(8) {
+            let mut byte = 0u8;
+            for (bit, flag,) in chunk.iter().enumerate() {
+                byte |= (*flag as u8) << bit;
+            }
+            buf.write(byte);
+        }
+    } }
+
+}
@@ -0,0 +1,58 @@
+//! The `PreEncoded` encode-once packet type.
+
+
+use crate::encode::{
+    PacketEncode,
+    PrefixedPacketEncode,
+    EncodeBuf
+};
+use core::ops::Deref;
+use alloc::boxed::Box;
+
+
+/// A value which has already been encoded, for packets which are sent many times, such as a packet broadcast to every player.
+///
+/// The value is encoded once when this is created, and encoding this again only copies the stored bytes.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PreEncoded(Box<[u8]>);
+
+impl PreEncoded {
+
+    /// Encodes `value` with [`PacketEncode`].
+    #[inline]
+    pub fn new<T>(value : &T) -> Self
+    where
+        T : PacketEncode + ?Sized
+    { Self(value.encode_to_vec().into_boxed_slice()) }
+
+    /// Encodes `packet` with [`PrefixedPacketEncode`], so that the stored bytes start with its packet ID.
+    #[inline]
+    pub fn new_prefixed<P>(packet : &P) -> Self
+    where
+        P : PrefixedPacketEncode + ?Sized
+    { Self(packet.encode_prefixed_to_vec().into_boxed_slice()) }
+
+    /// Returns the stored bytes.
+    #[inline(always)]
+    pub fn into_inner(self) -> Box<[u8]> { self.0 }
+
+}
+
+impl Deref for PreEncoded {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+/// Writes the stored bytes, without a [`VarInt`](crate::varint::VarInt) length.
+unsafe impl PacketEncode for PreEncoded {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.0.len() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write_slice(&self.0)
+    } }
+
+}
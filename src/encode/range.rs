@@ -0,0 +1,42 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use core::ops::{ Range, RangeInclusive };
+
+
+unsafe impl<T> PacketEncode for Range<T>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.start.encode_len() + self.end.encode_len()
+    }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.start.encode(buf);
+        self.end.encode(buf);
+    } }
+
+}
+
+unsafe impl<T> PacketEncode for RangeInclusive<T>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.start().encode_len() + self.end().encode_len()
+    }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.start().encode(buf);
+        self.end().encode(buf);
+    } }
+
+}
@@ -2,12 +2,14 @@ use crate::encode::{
     PacketEncode,
     EncodeBuf
 };
+use alloc::boxed::Box;
 
 
 unsafe impl<T> PacketEncode for &T
 where
     T : PacketEncode + ?Sized
 {
+    const FIXED_ENCODE_LEN : Option<usize> = T::FIXED_ENCODE_LEN;
 
     #[inline(always)]
     fn encode_len(&self) -> usize { T::encode_len(*self) }
@@ -17,6 +19,13 @@ where
         T::encode(*self, buf)
     } }
 
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { T::encode_to_writer(*self, writer) }
+
 }
 
 
@@ -24,6 +33,7 @@ unsafe impl<T> PacketEncode for &mut T
 where
     T : PacketEncode + ?Sized
 {
+    const FIXED_ENCODE_LEN : Option<usize> = T::FIXED_ENCODE_LEN;
 
     #[inline(always)]
     fn encode_len(&self) -> usize { T::encode_len(*self) }
@@ -33,4 +43,35 @@ where
         T::encode(*self, buf)
     } }
 
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { T::encode_to_writer(*self, writer) }
+
+}
+
+
+unsafe impl<T> PacketEncode for Box<T>
+where
+    T : PacketEncode + ?Sized
+{
+    const FIXED_ENCODE_LEN : Option<usize> = T::FIXED_ENCODE_LEN;
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { T::encode_len(self) }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        T::encode(self, buf)
+    } }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { T::encode_to_writer(self, writer) }
+
 }
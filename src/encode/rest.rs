@@ -0,0 +1,18 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::rest::RestOfPacket;
+
+
+unsafe impl PacketEncode for RestOfPacket {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.len() }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write_slice(self)
+    } }
+
+}
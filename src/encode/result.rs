@@ -0,0 +1,31 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+
+
+unsafe impl<T, E> PacketEncode for Result<T, E>
+where
+    T : PacketEncode,
+    E : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize { match (self) {
+        Ok(ok)   => 0u8.encode_len() + ok.encode_len(),
+        Err(err) => 1u8.encode_len() + err.encode_len()
+    } }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe { match (self) {
+        Ok(ok) => {
+            0u8.encode(buf);
+            ok.encode(buf);
+        },
+        Err(err) => {
+            1u8.encode(buf);
+            err.encode(buf);
+        }
+    } } }
+
+}
@@ -0,0 +1,51 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::varint::VarInt;
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+
+#[cfg(feature = "std")]
+unsafe impl<T, S> PacketEncode for HashSet<T, S>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt::<u32>(self.len() as u32).encode_len()
+        + self.iter().map(|item| item.encode_len()).sum::<usize>()
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.len() as u32).encode(buf);
+        for item in self {
+            item.encode(buf);
+        }
+    } }
+
+}
+
+
+unsafe impl<T> PacketEncode for BTreeSet<T>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt::<u32>(self.len() as u32).encode_len()
+        + self.iter().map(|item| item.encode_len()).sum::<usize>()
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.len() as u32).encode(buf);
+        for item in self {
+            item.encode(buf);
+        }
+    } }
+
+}
@@ -1,4 +1,4 @@
-//! `&[T]` encoder.
+//! `&[T]` and `[T; N]` encoders.
 
 
 use crate::encode::{
@@ -6,13 +6,18 @@ use crate::encode::{
     EncodeBuf
 };
 use crate::varint::VarInt;
-use core::{
-    any::TypeId,
-    ops::Deref
+use core::ops::Deref;
+use alloc::{
+    borrow::{ Cow, ToOwned },
+    collections::VecDeque,
+    sync::Arc,
+    vec::Vec
 };
-use std::borrow::Cow;
 
 
+/// Writes a [`VarInt`] length, followed by each item.
+///
+/// Slices of references, such as `&[&T]`, are encoded the same as `&[T]` through the `&T` encoder, so borrowed items do not need to be collected into a `Vec<T>` first.
 unsafe impl<T> PacketEncode for [T]
 where
     T : PacketEncode
@@ -20,8 +25,7 @@ where
 
     #[inline]
     fn encode_len(&self) -> usize {
-        VarInt::<u32>(self.len() as u32).encode_len()
-        + self.iter().map(|item| item.encode_len()).sum::<usize>() // TODO: Special case for `[u8]`.
+        VarInt::<u32>(self.len() as u32).encode_len().saturating_add(items_encode_len(self))
     }
 
     unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
@@ -31,23 +35,104 @@ where
         }
     } }
 
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        VarInt::<u32>(self.len() as u32).encode_to_writer(writer)?;
+        items_encode_to_writer(self, writer)
+    }
+
+}
+
+
+/// Returns the total number of bytes that the items in `slice` will write, without visiting every item if they have a fixed length.
+#[inline(always)]
+fn items_encode_len<T>(slice : &[T]) -> usize
+where
+    T : PacketEncode
+{
+    match (T::FIXED_ENCODE_LEN) {
+        Some(len) => slice.len().saturating_mul(len),
+        None      => slice.iter().map(|item| item.encode_len()).sum()
+    }
 }
 
 
+/// The maximum number of bytes buffered at once by [`items_encode_to_writer`].
+#[cfg(feature = "std")]
+const WRITER_CHUNK_LEN : usize = 8192;
+
+/// Writes the items in `slice` to `writer`.
+///
+/// Items with a fixed length are encoded in chunks of up to [`WRITER_CHUNK_LEN`] bytes, and all other items are written one at a time.
+#[cfg(feature = "std")]
+fn items_encode_to_writer<T, W>(slice : &[T], writer : &mut W) -> std::io::Result<()>
+where
+    T : PacketEncode,
+    W : std::io::Write + ?Sized
+{
+    match (T::FIXED_ENCODE_LEN) {
+        Some(0)   => { },
+        Some(len) => for chunk in slice.chunks((WRITER_CHUNK_LEN / len).max(1)) {
+            let mut buf = EncodeBuf::new(chunk.len() * len);
+            // SAFETY: Every item in `chunk` writes exactly `len` bytes, so `buf` has exactly enough space.
+            for item in chunk { unsafe { item.encode(&mut buf); } }
+            writer.write_all(buf.as_slice())?;
+        },
+        None => for item in slice {
+            item.encode_to_writer(writer)?;
+        }
+    }
+    Ok(())
+}
+
+
+/// Returns `slice` as a byte slice if each item is encoded as its own single byte, such as `u8`.
+#[inline(always)]
+fn as_bytes<T>(slice : &[T]) -> Option<&[u8]>
+where
+    T : PacketEncode
+{ T::items_as_bytes(slice) }
+
+
 unsafe impl<'l, T> PacketEncode for Cow<'l, [T]>
 where
     T   : PacketEncode + 'l,
     [T] : ToOwned
 {
 
-    #[inline(always)]
-    fn encode_len(&self) -> usize { <[T]>::encode_len(self) }
+    #[inline]
+    fn encode_len(&self) -> usize {
+        match (as_bytes(self)) {
+            Some(bytes) => EncodeBuf::len_prefixed_slice_len(bytes.len()),
+            None        => <[T]>::encode_len(self)
+        }
+    }
 
-    #[inline(always)]
+    #[inline]
     unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
-        <[T]>::encode(self, buf)
+        match (as_bytes(self)) {
+            Some(bytes) => buf.write_len_prefixed_slice(bytes),
+            None        => <[T]>::encode(self, buf)
+        }
     } }
 
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        match (as_bytes(self)) {
+            Some(bytes) => {
+                VarInt::<u32>(bytes.len() as u32).encode_to_writer(writer)?;
+                writer.write_all(bytes)
+            },
+            None => <[T]>::encode_to_writer(self, writer)
+        }
+    }
+
 }
 
 
@@ -56,6 +141,44 @@ where
     T : PacketEncode + 'static
 {
 
+    #[inline]
+    fn encode_len(&self) -> usize {
+        match (as_bytes(self)) {
+            Some(bytes) => EncodeBuf::len_prefixed_slice_len(bytes.len()),
+            None        => <[T]>::encode_len(self)
+        }
+    }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        match (as_bytes(self)) {
+            Some(bytes) => buf.write_len_prefixed_slice(bytes),
+            None        => <[T]>::encode(self, buf)
+        }
+    } }
+
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        match (as_bytes(self)) {
+            Some(bytes) => {
+                VarInt::<u32>(bytes.len() as u32).encode_to_writer(writer)?;
+                writer.write_all(bytes)
+            },
+            None => <[T]>::encode_to_writer(self, writer)
+        }
+    }
+
+}
+
+
+unsafe impl<T> PacketEncode for Arc<[T]>
+where
+    T : PacketEncode
+{
+
     #[inline(always)]
     fn encode_len(&self) -> usize { <[T]>::encode_len(self) }
 
@@ -64,6 +187,46 @@ where
         <[T]>::encode(self, buf)
     } }
 
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { <[T]>::encode_to_writer(self, writer) }
+
+}
+
+
+/// Encodes in the same way as `Vec<T>`, from front to back.
+unsafe impl<T> PacketEncode for VecDeque<T>
+where
+    T : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        let (front, back,) = self.as_slices();
+        VarInt::<u32>(self.len() as u32).encode_len().saturating_add(items_encode_len(front)).saturating_add(items_encode_len(back))
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.len() as u32).encode(buf);
+        for item in self {
+            item.encode(buf);
+        }
+    } }
+
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        let (front, back,) = self.as_slices();
+        VarInt::<u32>(self.len() as u32).encode_to_writer(writer)?;
+        items_encode_to_writer(front, writer)?;
+        items_encode_to_writer(back, writer)
+    }
+
 }
 
 
@@ -98,17 +261,159 @@ where
 
     #[inline]
     fn encode_len(&self) -> usize {
-        if (TypeId::of::<T>() == TypeId::of::<u8>()) {
-            self.len()
-        } else {
-            self.iter().map(|item| item.encode_len()).sum()
+        match (as_bytes(self)) {
+            Some(bytes) => bytes.len(),
+            None        => items_encode_len(self)
+        }
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        match (as_bytes(self)) {
+            Some(bytes) => buf.write_slice(bytes),
+            None        => for item in &**self {
+                item.encode(buf);
+            }
         }
+    } }
+
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    {
+        match (as_bytes(self)) {
+            Some(bytes) => writer.write_all(bytes),
+            None        => items_encode_to_writer(self, writer)
+        }
+    }
+
+}
+
+
+/// A `[T; N]` which will be encoded without a [`VarInt`] length.
+/// Decoders must know the length from context.
+///
+/// `UnprefixedArray<u8, N>` is the canonical decoder for fixed-size hashes and digests.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct UnprefixedArray<T, const N : usize>(pub [T; N]);
+
+impl<T, const N : usize> From<[T; N]> for UnprefixedArray<T, N> {
+    #[inline(always)]
+    fn from(value : [T; N]) -> Self { Self(value) }
+}
+
+impl<T, const N : usize> Deref for UnprefixedArray<T, N> {
+    type Target = [T; N];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+unsafe impl<T, const N : usize> PacketEncode for UnprefixedArray<T, N>
+where
+    T : PacketEncode
+{
+
+    const FIXED_ENCODE_LEN : Option<usize> = match (T::FIXED_ENCODE_LEN) {
+        Some(len) => Some(len * N),
+        None      => None
+    };
+
+    #[inline]
+    fn encode_len(&self) -> usize { items_encode_len(&self.0) }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        for item in &self.0 {
+            item.encode(buf);
+        }
+    } }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { items_encode_to_writer(&self.0, writer) }
+
+}
+
+
+/// A lazily produced sequence, which will be encoded with a [`VarInt`] length like a `Vec<T>` without being collected into one.
+///
+/// `iter` is cloned and walked once when the [`LazySlice`] is created to compute the length of the items, and again to encode them,
+///  so it should be cheap to clone.
+/// The clones must yield the same items every time.
+#[derive(Clone, Debug)]
+pub struct LazySlice<I>
+where
+    I                     : Iterator + Clone,
+    <I as Iterator>::Item : PacketEncode
+{
+    iter      : I,
+    len       : u32,
+    /// The total length of the items, as computed when the [`LazySlice`] was created.
+    items_len : usize
+}
+
+impl<I> LazySlice<I>
+where
+    I                     : Iterator + Clone,
+    <I as Iterator>::Item : PacketEncode
+{
+
+    /// Creates a new [`LazySlice`] of the `len` items yielded by `iter`.
+    ///
+    /// ### Panics
+    /// Panics if `len` does not fit in a [`VarInt::<u32>`](VarInt), or if the iterator does not yield exactly `len` items.
+    pub fn new(iter : I, len : usize) -> Self {
+        let len = u32::try_from(len).expect("`LazySlice` length does not fit in a `VarInt<u32>`");
+        let (count, items_len,) = iter.clone().fold((0usize, 0usize,), |(count, len,), item| (count + 1, len.saturating_add(item.encode_len()),));
+        assert_eq!(count, len as usize, "`LazySlice` iterator yielded a different number of items than its length");
+        Self { iter, len, items_len }
+    }
+
+}
+
+impl<I> LazySlice<I>
+where
+    I                     : ExactSizeIterator + Clone,
+    <I as Iterator>::Item : PacketEncode
+{
+
+    /// Creates a new [`LazySlice`] of the items yielded by `iter`, using its [`ExactSizeIterator::len`].
+    ///
+    /// ### Panics
+    /// Panics in the same cases as [`LazySlice::new`].
+    #[inline]
+    pub fn from_exact(iter : I) -> Self {
+        let len = iter.len();
+        Self::new(iter, len)
+    }
+
+}
+
+/// ### Panics
+/// Panics if a clone of the iterator yields items with a different total length than when the [`LazySlice`] was created.
+unsafe impl<I> PacketEncode for LazySlice<I>
+where
+    I                     : Iterator + Clone,
+    <I as Iterator>::Item : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt::<u32>(self.len).encode_len().saturating_add(self.items_len)
     }
 
     unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
-        for item in &**self {
+        // The items are produced again, so check that they match the first pass rather than trusting it.
+        let items_len = self.items_len;
+        VarInt::<u32>(self.len).encode(buf);
+        let start = buf.written();
+        for item in self.iter.clone().take(self.len as usize) {
+            assert!(item.encode_len() <= (items_len - (buf.written() - start)), "`LazySlice` iterator yielded different items when cloned");
             item.encode(buf);
         }
+        assert_eq!(buf.written() - start, items_len, "`LazySlice` iterator yielded different items when cloned");
     } }
 
 }
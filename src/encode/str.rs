@@ -2,22 +2,21 @@ use crate::encode::{
     PacketEncode,
     EncodeBuf
 };
-use crate::varint::VarInt;
-use std::borrow::Cow;
+use crate::decode::string::UnprefixedString;
+use alloc::{
+    borrow::Cow,
+    string::String
+};
 
 
 unsafe impl PacketEncode for str {
 
     #[inline]
-    fn encode_len(&self) -> usize {
-        VarInt::<u32>(self.len() as u32).encode_len()
-        + self.len()
-    }
+    fn encode_len(&self) -> usize { EncodeBuf::len_prefixed_slice_len(self.len()) }
 
     #[inline]
     unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
-        VarInt::<u32>(self.len() as u32).encode(buf);
-        buf.write_slice(self.as_bytes());
+        buf.write_len_prefixed_slice(self.as_bytes());
     } }
 
 }
@@ -47,3 +46,16 @@ unsafe impl PacketEncode for String {
     } }
 
 }
+
+
+unsafe impl PacketEncode for UnprefixedString {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { self.len() }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write_slice(self.as_bytes())
+    } }
+
+}
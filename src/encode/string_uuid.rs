@@ -0,0 +1,19 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::string_uuid::StringUuid;
+use uuid::fmt::Hyphenated;
+
+
+unsafe impl PacketEncode for StringUuid {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { EncodeBuf::len_prefixed_slice_len(Hyphenated::LENGTH) }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        let mut string = [0u8; Hyphenated::LENGTH];
+        buf.write_len_prefixed_slice(self.0.hyphenated().encode_lower(&mut string).as_bytes());
+    } }
+
+}
@@ -0,0 +1,19 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::tristate::Tristate;
+
+
+unsafe impl PacketEncode for Tristate {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(1);
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { 1 }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write(self.to_byte())
+    } }
+
+}
@@ -41,7 +41,21 @@ macro impl_packetencode_for_tuple($($generics:ident),* $(,)?) {
             }
         }
 
+        #[cfg(feature = "std")]
+        fn encode_to_writer<W>(&self,
+            #[allow(unused_variables)]
+            writer : &mut W
+        ) -> std::io::Result<()>
+        where
+            W : std::io::Write + ?Sized
+        {
+            #[allow(non_snake_case)]
+            let ( $( $generics , )* ) = self;
+            $( $generics.encode_to_writer(writer)?; )*
+            Ok(())
+        }
+
     }
 }
 
-impl_packetencode_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L,);
+impl_packetencode_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P,);
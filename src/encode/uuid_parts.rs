@@ -0,0 +1,38 @@
+use crate::encode::{
+    PacketEncode,
+    EncodeBuf
+};
+use crate::decode::uuid_parts::{
+    UuidMostLeast,
+    UuidIntArray
+};
+
+
+unsafe impl PacketEncode for UuidMostLeast {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { size_of::<i64>() * 2 }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        let (most, least,) = self.to_most_least();
+        most.encode(buf);
+        least.encode(buf);
+    } }
+
+}
+
+
+unsafe impl PacketEncode for UuidIntArray {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { size_of::<i32>() * 4 }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        for int in self.to_ints() {
+            int.encode(buf);
+        }
+    } }
+
+}
@@ -0,0 +1,69 @@
+//! The legacy fixed-point coordinate type.
+
+
+use crate::{
+    decode::{
+        PacketDecode,
+        DecodeIter,
+        IncompleteDecodeError
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    }
+};
+use core::f64::math;
+
+
+/// A coordinate which is sent as an `i32` with 5 fractional bits, as used by pre-1.9 and some compatibility packets.
+///
+/// The encoded value is the coordinate multiplied by 32, so the precision is 1/32 of a block (`0.03125`).
+/// Encoding rounds to the nearest step, with halfway values rounded away from zero,
+///  coordinates outside of the range of an `i32` are saturated, and NaN is encoded as `0`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Default, Debug)]
+pub struct FixedPoint5(pub f64);
+
+impl FixedPoint5 {
+
+    /// The number of steps per block.
+    pub const SCALE : f64 = 32.0;
+
+    /// Creates a new [`FixedPoint5`] from its encoded `i32`.
+    #[inline(always)]
+    pub fn from_raw(raw : i32) -> Self { Self((raw as f64) / Self::SCALE) }
+
+    /// Returns the `i32` that this coordinate is encoded as, rounding to the nearest step.
+    #[inline(always)]
+    pub fn to_raw(self) -> i32 { math::round(self.0 * Self::SCALE) as i32 }
+
+}
+
+impl From<f64> for FixedPoint5 {
+    #[inline(always)]
+    fn from(value : f64) -> Self { Self(value) }
+}
+
+
+impl PacketDecode for FixedPoint5 {
+    type Error = IncompleteDecodeError;
+    const MIN_ENCODED_LEN : usize = size_of::<i32>();
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self::from_raw(i32::decode(iter)?)) }
+}
+
+unsafe impl PacketEncode for FixedPoint5 {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<i32>());
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { size_of::<i32>() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.to_raw().encode(buf)
+    } }
+
+}
@@ -0,0 +1,167 @@
+//! Bitmask flag types.
+
+
+use crate::{
+    decode::{
+        PacketDecode,
+        DecodeIter,
+        IncompleteDecodeError
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    },
+    varint::{
+        VarInt,
+        VarIntDecodeError
+    }
+};
+
+
+/// A byte of packed flags, such as entity states and player abilities.
+///
+/// Flag `0` is the least significant bit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Flags8(pub u8);
+
+impl Flags8 {
+
+    /// No flags are set.
+    pub const EMPTY : Self = Self(0);
+
+    /// The number of flags.
+    pub const BITS : u32 = u8::BITS;
+
+    /// Returns `true` if flag `bit` is set.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags8::BITS`].
+    #[inline(always)]
+    pub const fn has(self, bit : u32) -> bool {
+        assert!(bit < Self::BITS, "flag bit out of range");
+        (self.0 & (1 << bit)) != 0
+    }
+
+    /// Sets or clears flag `bit`.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags8::BITS`].
+    #[inline(always)]
+    pub const fn set(&mut self, bit : u32, value : bool) {
+        *self = self.with(bit, value);
+    }
+
+    /// Returns these flags, with flag `bit` set or cleared.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags8::BITS`].
+    #[inline(always)]
+    pub const fn with(self, bit : u32, value : bool) -> Self {
+        assert!(bit < Self::BITS, "flag bit out of range");
+        if (value) { Self(self.0 | (1 << bit)) } else { Self(self.0 & (! (1 << bit))) }
+    }
+
+}
+
+impl From<u8> for Flags8 {
+    #[inline(always)]
+    fn from(value : u8) -> Self { Self(value) }
+}
+
+
+impl PacketDecode for Flags8 {
+    type Error = IncompleteDecodeError;
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(iter.read()?)) }
+}
+
+unsafe impl PacketEncode for Flags8 {
+    const FIXED_ENCODE_LEN : Option<usize> = Some(size_of::<u8>());
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { size_of::<u8>() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        buf.write(self.0)
+    } }
+
+}
+
+
+/// 32 packed flags, sent as a [`VarInt<u32>`](VarInt).
+///
+/// Flag `0` is the least significant bit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Flags32(pub u32);
+
+impl Flags32 {
+
+    /// No flags are set.
+    pub const EMPTY : Self = Self(0);
+
+    /// The number of flags.
+    pub const BITS : u32 = u32::BITS;
+
+    /// Returns `true` if flag `bit` is set.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags32::BITS`].
+    #[inline(always)]
+    pub const fn has(self, bit : u32) -> bool {
+        assert!(bit < Self::BITS, "flag bit out of range");
+        (self.0 & (1 << bit)) != 0
+    }
+
+    /// Sets or clears flag `bit`.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags32::BITS`].
+    #[inline(always)]
+    pub const fn set(&mut self, bit : u32, value : bool) {
+        *self = self.with(bit, value);
+    }
+
+    /// Returns these flags, with flag `bit` set or cleared.
+    ///
+    /// ### Panics
+    /// Panics if `bit` is not less than [`Flags32::BITS`].
+    #[inline(always)]
+    pub const fn with(self, bit : u32, value : bool) -> Self {
+        assert!(bit < Self::BITS, "flag bit out of range");
+        if (value) { Self(self.0 | (1 << bit)) } else { Self(self.0 & (! (1 << bit))) }
+    }
+
+}
+
+impl From<u32> for Flags32 {
+    #[inline(always)]
+    fn from(value : u32) -> Self { Self(value) }
+}
+
+
+impl PacketDecode for Flags32 {
+    type Error = VarIntDecodeError;
+
+    #[inline(always)]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Ok(Self(*VarInt::<u32>::decode(iter)?)) }
+}
+
+unsafe impl PacketEncode for Flags32 {
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { VarInt::<u32>(self.0).encode_len() }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.0).encode(buf)
+    } }
+
+}
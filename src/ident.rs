@@ -0,0 +1,169 @@
+//! The namespaced identifier type.
+
+
+use crate::{
+    decode::{
+        PacketDecode,
+        DecodeIter,
+        string::StringDecodeError
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    },
+    varint::VarInt
+};
+use core::fmt::{ self, Display, Formatter };
+use alloc::string::String;
+
+
+/// A namespaced identifier, such as `minecraft:stone`.
+///
+/// Identifiers are encoded as a `String` of the form `namespace:path`.
+/// When decoding, an identifier without a namespace uses [`Identifier::DEFAULT_NAMESPACE`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Identifier {
+    namespace : String,
+    path      : String
+}
+
+impl Identifier {
+
+    /// The namespace used when an identifier does not have a namespace.
+    pub const DEFAULT_NAMESPACE : &'static str = "minecraft";
+
+    /// Creates a new [`Identifier`], checking that `namespace` and `path` are valid.
+    pub fn new(namespace : impl Into<String>, path : impl Into<String>) -> Result<Self, InvalidIdentifierError> {
+        let namespace = namespace.into();
+        let path      = path.into();
+        if (! Self::is_valid_namespace(&namespace)) { return Err(InvalidIdentifierError::Namespace); }
+        if (! Self::is_valid_path(&path)) { return Err(InvalidIdentifierError::Path); }
+        Ok(Self { namespace, path })
+    }
+
+    /// Parses an [`Identifier`] of the form `namespace:path` or `path`.
+    ///
+    /// If there is no namespace, [`Identifier::DEFAULT_NAMESPACE`] is used.
+    #[inline]
+    pub fn parse(string : &str) -> Result<Self, InvalidIdentifierError> {
+        Self::parse_with_namespace(string, Self::DEFAULT_NAMESPACE)
+    }
+
+    /// Parses an [`Identifier`] of the form `namespace:path` or `path`.
+    ///
+    /// If there is no namespace, `default_namespace` is used.
+    pub fn parse_with_namespace(string : &str, default_namespace : &str) -> Result<Self, InvalidIdentifierError> {
+        match (string.split_once(':')) {
+            Some((namespace, path,)) => Self::new(namespace, path),
+            None                     => Self::new(default_namespace, string)
+        }
+    }
+
+    /// Returns the namespace of this identifier.
+    #[inline(always)]
+    pub fn namespace(&self) -> &str { &self.namespace }
+
+    /// Returns the path of this identifier.
+    #[inline(always)]
+    pub fn path(&self) -> &str { &self.path }
+
+    /// Returns `true` if `namespace` only contains `a-z`, `0-9`, `.`, `-`, and `_`.
+    pub fn is_valid_namespace(namespace : &str) -> bool {
+        (! namespace.is_empty()) && namespace.bytes().all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_'))
+    }
+
+    /// Returns `true` if `path` only contains `a-z`, `0-9`, `.`, `-`, `_`, and `/`.
+    pub fn is_valid_path(path : &str) -> bool {
+        (! path.is_empty()) && path.bytes().all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'/'))
+    }
+
+    /// Decodes an [`Identifier`] from a byte iterator.
+    ///
+    /// If the identifier does not have a namespace, `default_namespace` is used.
+    pub fn decode_with_namespace<I>(iter : &mut DecodeIter<I>, default_namespace : &str) -> Result<Self, IdentifierDecodeError>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        if (! Self::is_valid_namespace(default_namespace)) {
+            return Err(IdentifierDecodeError::DefaultNamespace);
+        }
+        let string = String::decode(iter)?;
+        Ok(Self::parse_with_namespace(&string, default_namespace)?)
+    }
+
+}
+
+impl Display for Identifier {
+    #[inline]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "{}:{}", self.namespace, self.path) }
+}
+
+
+impl PacketDecode for Identifier {
+    type Error = IdentifierDecodeError;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    { Self::decode_with_namespace(iter, Self::DEFAULT_NAMESPACE) }
+}
+
+unsafe impl PacketEncode for Identifier {
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        EncodeBuf::len_prefixed_slice_len(self.namespace.len() + 1 + self.path.len())
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>((self.namespace.len() + 1 + self.path.len()) as u32).encode(buf);
+        buf.write_slice(self.namespace.as_bytes());
+        buf.write(b':');
+        buf.write_slice(self.path.as_bytes());
+    } }
+
+}
+
+
+/// Returned when an [`Identifier`] has an invalid namespace or path.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InvalidIdentifierError {
+    /// The namespace was empty or contained invalid characters.
+    Namespace,
+    /// The path was empty or contained invalid characters.
+    Path
+}
+impl Display for InvalidIdentifierError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Namespace => write!(f, "invalid namespace"),
+        Self::Path      => write!(f, "invalid path")
+    } }
+}
+
+
+/// Returned by packet decoders when an [`Identifier`] was not decoded successfully.
+#[derive(Debug)]
+pub enum IdentifierDecodeError {
+    /// The string failed to decode.
+    String(StringDecodeError),
+    /// The decoded string was not a valid identifier.
+    Invalid(InvalidIdentifierError),
+    /// The default namespace given to [`Identifier::decode_with_namespace`] was not valid.
+    DefaultNamespace
+}
+impl From<StringDecodeError> for IdentifierDecodeError {
+    #[inline(always)]
+    fn from(err : StringDecodeError) -> Self { Self::String(err) }
+}
+impl From<InvalidIdentifierError> for IdentifierDecodeError {
+    #[inline(always)]
+    fn from(err : InvalidIdentifierError) -> Self { Self::Invalid(err) }
+}
+impl Display for IdentifierDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::String(err)      => err.fmt(f),
+        Self::Invalid(err)     => err.fmt(f),
+        Self::DefaultNamespace => write!(f, "invalid default namespace")
+    } }
+}
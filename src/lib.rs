@@ -1,22 +1,43 @@
 #![doc = include_str!("../README.md")]
 
 
+#![no_std]
+
 #![feature(
 
     // Syntax
     decl_macro,
 
+    // Type system
+    adt_const_params,
+
     // Standard library
+    core_float_math,
     iter_next_chunk,
     maybe_uninit_array_assume_init
 
 )]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod address;
+pub mod angle;
 pub mod decode;
 pub mod encode;
+pub mod fixed_point;
+pub mod flags;
+pub mod ident;
 pub mod meta;
+#[cfg(feature = "observer")]
+pub mod observer;
+pub mod packet_enum;
+pub mod packet_registry;
+pub mod registry;
 
 pub mod varint;
+pub mod varint_enum;
 
 pub use uuid;
 #[cfg(feature = "chrono")]
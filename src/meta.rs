@@ -2,30 +2,42 @@
 
 
 use core::{
-    mem::transmute,
-    sync::atomic::{
-        AtomicU8,
-        Ordering as AtomicOrdering
-    }
+    fmt::{ self, Display, Formatter },
+    marker::ConstParamTy,
+    sync::atomic::Ordering as AtomicOrdering
 };
+#[cfg(not(loom))]
+use core::sync::atomic::AtomicU16;
+#[cfg(loom)]
+use loom::sync::atomic::AtomicU16;
 
 
 /// Packet metadata.
 pub trait PacketMeta {
     /// The state in which this packet will be sent.
-    const STATE  : PacketState;
+    const STATE         : PacketState;
     /// The direction that this packet will be sent.
-    const BOUND  : PacketBound;
+    const BOUND         : PacketBound;
     /// This ID of this packet.
-    const PREFIX : u8;
+    ///
+    /// Unless [`VARINT_PREFIX`](Self::VARINT_PREFIX) is set, this must be no greater than [`u8::MAX`].
+    const PREFIX        : u32;
+    /// Whether the ID of this packet is written as a [`VarInt<u32>`](crate::varint::VarInt) instead of a single byte.
+    const VARINT_PREFIX : bool         = false;
     /// Whether this packet will kick the player from the server.
-    const KICK   : bool        = false;
+    ///
+    /// With the `observer` feature, this is reported to the packet observer, so that the connection can be flushed and closed.
+    const KICK          : bool         = false;
+    /// A human-readable name for this packet, used in errors and by debug tooling.
+    const LABEL         : &'static str = "<unknown>";
 }
 
 
 /// The state in which a packet will be sent.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-#[repr(u8)]
+///
+/// States added by future protocol versions, proxies, or other custom protocols can be represented as [`Custom`](Self::Custom).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, ConstParamTy)]
+#[non_exhaustive]
 pub enum PacketState {
 
     /// Handshaking
@@ -41,30 +53,122 @@ pub enum PacketState {
     Config,
 
     /// Play
-    Play
+    Play,
+
+    /// A state which is not part of the vanilla protocol.
+    Custom(u8)
+
+}
+
+impl PacketState {
+
+    /// The number of vanilla states, which is the length of arrays indexed by [`index`](Self::index).
+    pub const COUNT : usize = 5;
+
+    /// Every vanilla state, in order of [`index`](Self::index).
+    pub const VANILLA : [Self; Self::COUNT] = [Self::Handshake, Self::Status, Self::Login, Self::Config, Self::Play];
+
+    /// Returns the ordinal of a vanilla state, or `None` for a [`Custom`](Self::Custom) state.
+    ///
+    /// Ordinals are contiguous from `0` to [`COUNT`](Self::COUNT) and will not change, so they can be used to index a `[T; PacketState::COUNT]`.
+    /// They are the same ordinals accepted by `PacketState::try_from(u8)`.
+    #[inline(always)]
+    pub const fn index(self) -> Option<usize> { match (self) {
+        Self::Custom(_) => None,
+        state           => Some(state.to_bits() as usize)
+    } }
+
+    /// The bit set in [`to_bits`](Self::to_bits) for [`Custom`](Self::Custom) states.
+    const CUSTOM_BIT : u16 = 0x0100;
+
+    /// Converts this state into a `u16`, which can be converted back with [`from_bits`](Self::from_bits).
+    #[inline(always)]
+    pub(crate) const fn to_bits(self) -> u16 { match (self) {
+        Self::Handshake     => 0,
+        Self::Status        => 1,
+        Self::Login         => 2,
+        Self::Config        => 3,
+        Self::Play          => 4,
+        Self::Custom(state) => Self::CUSTOM_BIT | (state as u16)
+    } }
+
+    /// Converts a value returned by [`to_bits`](Self::to_bits) back into a `PacketState`.
+    ///
+    /// ### Panics
+    /// Panics if `bits` was not returned by [`to_bits`](Self::to_bits).
+    #[inline(always)]
+    const fn from_bits(bits : u16) -> Self { match (bits) {
+        0 => Self::Handshake,
+        1 => Self::Status,
+        2 => Self::Login,
+        3 => Self::Config,
+        4 => Self::Play,
+        _ if ((bits & (! 0x00FF)) == Self::CUSTOM_BIT) => Self::Custom((bits & 0x00FF) as u8),
+        _ => panic!("invalid `PacketState` bits")
+    } }
+
+}
+
+impl TryFrom<u8> for PacketState {
+    type Error = InvalidPacketStateError;
 
+    fn try_from(ordinal : u8) -> Result<Self, Self::Error> { match (ordinal) {
+        0 => Ok(Self::Handshake),
+        1 => Ok(Self::Status),
+        2 => Ok(Self::Login),
+        3 => Ok(Self::Config),
+        4 => Ok(Self::Play),
+        _ => Err(InvalidPacketStateError(ordinal))
+    } }
+}
+
+
+/// Returned when converting a byte which is not a valid ordinal into a vanilla [`PacketState`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidPacketStateError(pub u8);
+
+impl Display for InvalidPacketStateError {
+    #[inline(always)]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { write!(f, "invalid packet state {}", self.0) }
 }
 
 
 /// A `PacketState` which can be safely shared between threads.
 ///
-/// This type has the same size, alignment, and bit validity as a [`u8`].
+/// This type has the same size, alignment, and bit validity as a [`u16`].
 #[derive(Debug)]
 #[repr(transparent)]
-pub struct AtomicPacketState(AtomicU8);
+pub struct AtomicPacketState(AtomicU16);
 impl AtomicPacketState {
 
     /// Creates a new atomic packet state.
+    #[cfg(not(loom))]
     #[inline(always)]
     pub const fn new(state : PacketState) -> Self {
-        Self(AtomicU8::new(state as u8))
+        Self(AtomicU16::new(state.to_bits()))
+    }
+
+    /// Creates a new atomic packet state.
+    ///
+    /// Loom atomics can not be created in constants.
+    #[cfg(loom)]
+    #[inline(always)]
+    pub fn new(state : PacketState) -> Self {
+        Self(AtomicU16::new(state.to_bits()))
     }
 
     /// Consumes the atomic and reutrns the contained value.
+    #[cfg(not(loom))]
     #[inline(always)]
     pub const fn into_inner(self) -> PacketState {
-        // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-        unsafe { transmute::<u8, PacketState>(self.0.into_inner()) }
+        PacketState::from_bits(self.0.into_inner())
+    }
+
+    /// Consumes the atomic and reutrns the contained value.
+    #[cfg(loom)]
+    #[inline(always)]
+    pub fn into_inner(self) -> PacketState {
+        PacketState::from_bits(self.0.into_inner())
     }
 
     /// Loads a value from the atomic.
@@ -73,8 +177,7 @@ impl AtomicPacketState {
     /// Panics if `order` is [`Release`](AtomicOrdering::Release) or [`AcqRel`](AtomicOrdering::AcqRel).
     #[inline(always)]
     pub fn load(&self, order : AtomicOrdering) -> PacketState {
-        // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-        unsafe { transmute::<u8, PacketState>(self.0.load(order)) }
+        PacketState::from_bits(self.0.load(order))
     }
 
     /// Stores a value into the atomic.
@@ -83,14 +186,13 @@ impl AtomicPacketState {
     /// Panics if `order` is [`Acquire`](AtomicOrdering::Acquire) or [`AcqRel`](AtomicOrdering::AcqRel).
     #[inline(always)]
     pub fn store(&self, val : PacketState, order : AtomicOrdering) {
-        self.0.store(val as u8, order)
+        self.0.store(val.to_bits(), order)
     }
 
     /// Stores a value into the atomic, returning the previous value.
     #[inline(always)]
     pub fn swap(&self, val : PacketState, order : AtomicOrdering) -> PacketState {
-        // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-        unsafe { transmute::<u8, PacketState>(self.0.swap(val as u8, order)) }
+        PacketState::from_bits(self.0.swap(val.to_bits(), order))
     }
 
     /// Stores a value into the atomic if the current value is the same as the `current` value.
@@ -99,10 +201,9 @@ impl AtomicPacketState {
     /// On success this value is guaranteed to be equal to `current`.
     #[inline(always)]
     pub fn compare_exchange(&self, current : PacketState, new : PacketState, success : AtomicOrdering, failure : AtomicOrdering) -> Result<PacketState, PacketState> {
-        self.0.compare_exchange(current as u8, new as u8, success, failure)
-            // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-            .map     (|v| unsafe { transmute::<u8, PacketState>(v) })
-            .map_err (|v| unsafe { transmute::<u8, PacketState>(v) })
+        self.0.compare_exchange(current.to_bits(), new.to_bits(), success, failure)
+            .map     (PacketState::from_bits)
+            .map_err (PacketState::from_bits)
     }
 
     /// Stores a value into the atomic if the current value is the same as the `current` value.
@@ -111,10 +212,9 @@ impl AtomicPacketState {
     /// The return value is a result indicaing whether the new value was written and containing the previous value.
     #[inline(always)]
     pub fn compare_exchange_weak(&self, current : PacketState, new : PacketState, success : AtomicOrdering, failure : AtomicOrdering) -> Result<PacketState, PacketState> {
-        self.0.compare_exchange(current as u8, new as u8, success, failure)
-            // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-            .map     (|v| unsafe { transmute::<u8, PacketState>(v) })
-            .map_err (|v| unsafe { transmute::<u8, PacketState>(v) })
+        self.0.compare_exchange_weak(current.to_bits(), new.to_bits(), success, failure)
+            .map     (PacketState::from_bits)
+            .map_err (PacketState::from_bits)
     }
 
     /// Fetches the value, and applies a function to it that returns an optional new value.
@@ -122,17 +222,40 @@ impl AtomicPacketState {
     ///
     /// Note: This may call the function multiple times if the value has been changed from other threads in the meantime,
     ///  as long as the function `Some(_)`, but the function will have been applied only once to the stored value.
+    ///
+    /// `set_order` is used when the new value is stored, and `fetch_order` is used when the value is loaded, including by failed attempts to store.
+    /// For a plain state change, [`transition`](Self::transition) is usually what is wanted instead.
+    ///
+    /// ### Panics
+    /// Panics if `fetch_order` is [`Release`](AtomicOrdering::Release) or [`AcqRel`](AtomicOrdering::AcqRel).
+    /// In debug builds, this is checked before `f` is called.
     #[inline(always)]
     pub fn fetch_update<F>(&self, set_order : AtomicOrdering, fetch_order : AtomicOrdering, mut f : F) -> Result<PacketState, PacketState>
     where
         F : FnMut(PacketState) -> Option<PacketState>
     {
-        // SAFETY: This is safe because the inner value of the `AtomicU8` will always be a valid `PacketState` ordinal.
-        self.0.fetch_update(set_order, fetch_order, |v| f(unsafe { transmute::<u8, PacketState>(v) }).map(|s| s as u8))
-            .map     (|v| unsafe { transmute::<u8, PacketState>(v) })
-            .map_err (|v| unsafe { transmute::<u8, PacketState>(v) })
+        debug_assert!(is_load_order(fetch_order), "`AtomicPacketState::fetch_update` was given a `fetch_order` of {fetch_order:?}, which can not be used for loads");
+        self.0.fetch_update(set_order, fetch_order, |v| f(PacketState::from_bits(v)).map(PacketState::to_bits))
+            .map     (PacketState::from_bits)
+            .map_err (PacketState::from_bits)
     }
 
+    /// Changes the state from `from` to `to`, if it is currently `from`.
+    ///
+    /// Returns `true` if the state was changed.
+    /// This uses [`AcqRel`](AtomicOrdering::AcqRel) ordering on success and [`Acquire`](AtomicOrdering::Acquire) on failure,
+    ///  so writes made before a successful transition are visible to any thread which later observes `to`.
+    #[inline(always)]
+    pub fn transition(&self, from : PacketState, to : PacketState) -> bool {
+        self.0.compare_exchange(from.to_bits(), to.to_bits(), AtomicOrdering::AcqRel, AtomicOrdering::Acquire).is_ok()
+    }
+
+}
+
+/// Returns `true` if `order` can be used for an atomic load.
+#[inline(always)]
+fn is_load_order(order : AtomicOrdering) -> bool {
+    ! matches!(order, AtomicOrdering::Release | AtomicOrdering::AcqRel)
 }
 
 
@@ -0,0 +1,99 @@
+//! Hooks for observing packets as they are en/decoded.
+
+
+use crate::meta::{
+    PacketMeta,
+    PacketState,
+    PacketBound
+};
+use std::sync::OnceLock;
+
+
+/// Information about a packet which was en/decoded through [`PrefixedPacketEncode`](crate::encode::PrefixedPacketEncode)
+///  or [`PrefixedPacketDecode`](crate::decode::PrefixedPacketDecode).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct ObservedPacket {
+    /// The state in which the packet was sent.
+    pub state  : PacketState,
+    /// The direction that the packet was sent.
+    pub bound  : PacketBound,
+    /// The ID of the packet.
+    pub prefix : u32,
+    /// The [`PacketMeta::LABEL`] of the packet.
+    pub label  : &'static str,
+    /// The [`PacketMeta::KICK`] of the packet.
+    ///
+    /// When this is set on an encoded packet, the connection should be flushed and closed once it has been sent.
+    pub kick   : bool,
+    /// The number of bytes in the packet, including the ID.
+    pub len    : usize
+}
+
+impl ObservedPacket {
+
+    #[inline(always)]
+    fn of<P>(len : usize) -> Self
+    where
+        P : PacketMeta
+    { Self {
+        state  : P::STATE,
+        bound  : P::BOUND,
+        prefix : P::PREFIX,
+        label  : P::LABEL,
+        kick   : P::KICK,
+        len
+    } }
+
+}
+
+
+/// Observes every packet which is en/decoded through [`PrefixedPacketEncode`](crate::encode::PrefixedPacketEncode)
+///  or [`PrefixedPacketDecode`](crate::decode::PrefixedPacketDecode).
+///
+/// All methods do nothing by default.
+pub trait PacketObserver
+where
+    Self : Sync
+{
+
+    /// Called after a packet was successfully encoded.
+    #[inline(always)]
+    fn encoded(&self, packet : &ObservedPacket) { _ = packet; }
+
+    /// Called after a packet was successfully decoded.
+    #[inline(always)]
+    fn decoded(&self, packet : &ObservedPacket) { _ = packet; }
+
+}
+
+
+static OBSERVER : OnceLock<&'static dyn PacketObserver> = OnceLock::new();
+
+/// Sets the global [`PacketObserver`].
+///
+/// The observer can only be set once. If it has already been set, `observer` is returned in `Err`.
+#[inline]
+pub fn set_observer(observer : &'static dyn PacketObserver) -> Result<(), &'static dyn PacketObserver> {
+    OBSERVER.set(observer)
+}
+
+#[inline]
+pub(crate) fn observe_encoded<P>(len : usize)
+where
+    P : PacketMeta
+{
+    if let Some(observer) = OBSERVER.get() {
+        observer.encoded(&ObservedPacket::of::<P>(len));
+    }
+}
+
+#[inline]
+pub(crate) fn observe_decoded<P>(len : usize)
+where
+    P : PacketMeta
+{
+    if let Some(observer) = OBSERVER.get() {
+        observer.decoded(&ObservedPacket::of::<P>(len));
+    }
+}
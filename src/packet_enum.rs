@@ -0,0 +1,127 @@
+//! Enums of packets, en/decoded by their packet ID.
+
+
+use crate::meta::PacketMeta;
+
+
+/// Reports a packet decoded by [`packet_enum`] to the [observer](crate::observer), if the `observer` feature is enabled.
+#[doc(hidden)]
+#[inline(always)]
+pub fn observe_decoded<P>(#[allow(unused_variables)] len : usize)
+where
+    P : PacketMeta
+{
+    #[cfg(feature = "observer")]
+    crate::observer::observe_decoded::<P>(len);
+}
+
+
+/// Defines an enum whose variants each wrap a packet type, such as every serverbound packet in the play state.
+///
+/// The enum implements [`PrefixedPacketEncode`](crate::encode::PrefixedPacketEncode), which encodes the wrapped packet with its ID,
+///  and [`PrefixedPacketDecode`](crate::decode::PrefixedPacketDecode), which reads a packet ID and decodes the variant with that ID.
+/// Every wrapped type must implement [`PacketMeta`], [`PacketEncode`](crate::encode::PacketEncode),
+///  and [`PacketDecode`](crate::decode::PacketDecode), and all of them must agree on [`PacketMeta::VARINT_PREFIX`].
+/// No two wrapped types may have the same [`PREFIX`](PacketMeta::PREFIX), as only the packet ID is read when decoding,
+///  so the later one could never be decoded, even if their [`STATE`](PacketMeta::STATE)s or [`BOUND`](PacketMeta::BOUND)s differ.
+/// Both of these are checked at compile time.
+///
+/// The name after the `:` is used for a generated error enum, with a variant holding each wrapped type's decode error.
+/// Each wrapped type's decode error must implement [`Display`](core::fmt::Display).
+/// A packet ID which does not match any variant returns [`PrefixedDecodeError::UnknownPrefix`](crate::decode::PrefixedDecodeError::UnknownPrefix).
+pub macro packet_enum(
+    $( #[$meta:meta] )*
+    $vis:vis enum $ident:ident : $error:ident {
+        $( $( #[$variant_meta:meta] )* $variant:ident ( $ty:ty ) ),+ $(,)?
+    }
+) {
+
+    $( #[$meta] )*
+    $vis enum $ident {
+        $( $( #[$variant_meta] )* $variant($ty) , )+
+    }
+
+    const _ : () = {
+        let ids = [ $( <$ty as $crate::meta::PacketMeta>::PREFIX , )+ ];
+        let mut i = 0;
+        while (i < ids.len()) {
+            let mut j = i + 1;
+            while (j < ids.len()) {
+                assert!(ids[i] != ids[j], "every packet in a `packet_enum` must have a different `PacketMeta::PREFIX`");
+                j += 1;
+            }
+            i += 1;
+        }
+    };
+
+    $( impl From<$ty> for $ident {
+        #[inline(always)]
+        fn from(packet : $ty) -> Self { Self::$variant(packet) }
+    } )+
+
+    unsafe impl $crate::encode::PrefixedPacketEncode for $ident {
+
+        #[inline]
+        fn encode_prefixed_len(&self) -> usize { match (self) {
+            $( Self::$variant(packet) => <$ty as $crate::encode::PrefixedPacketEncode>::encode_prefixed_len(packet) , )+
+        } }
+
+        #[inline]
+        unsafe fn encode_prefixed(&self, buf : &mut $crate::encode::EncodeBuf) { unsafe { match (self) {
+            $( Self::$variant(packet) => <$ty as $crate::encode::PrefixedPacketEncode>::encode_prefixed(packet, buf) , )+
+        } } }
+
+    }
+
+    impl $crate::decode::PrefixedPacketDecode for $ident {
+        type Error = $crate::decode::PrefixedDecodeError<$error>;
+
+        fn decode_prefixed<I>(iter : &mut $crate::decode::DecodeIter<I>) -> Result<Self, Self::Error>
+        where
+            I : ExactSizeIterator<Item = u8>
+        {
+            const VARINT_PREFIX : bool = {
+                let flags = [ $( <$ty as $crate::meta::PacketMeta>::VARINT_PREFIX , )+ ];
+                let mut i = 1;
+                while (i < flags.len()) {
+                    assert!(flags[i] == flags[0], "every packet in a `packet_enum` must have the same `PacketMeta::VARINT_PREFIX`");
+                    i += 1;
+                }
+                flags[0]
+            };
+            let start  = iter.consumed();
+            let prefix = if (VARINT_PREFIX) {
+                *<$crate::varint::VarInt<u32> as $crate::decode::PacketDecode>::decode(iter)
+                    .map_err($crate::decode::PrefixedDecodeError::Prefix)?
+            } else {
+                iter.read().map_err(|err| $crate::decode::PrefixedDecodeError::Prefix($crate::varint::VarIntDecodeError::Incomplete(err)))? as u32
+            };
+            $( if (prefix == <$ty as $crate::meta::PacketMeta>::PREFIX) {
+                let packet = <$ty as $crate::decode::PacketDecode>::decode(iter)
+                    .map_err(|err| $crate::decode::PrefixedDecodeError::Error($error::$variant(err)))?;
+                $crate::packet_enum::observe_decoded::<$ty>(iter.consumed() - start);
+                return Ok(Self::$variant(packet));
+            } )+
+            Err($crate::decode::PrefixedDecodeError::UnknownPrefix {
+                found    : prefix,
+                expected : None,
+                state    : None,
+                label    : stringify!($ident)
+            })
+        }
+    }
+
+    #[doc = concat!("Returned by packet decoders when a [`", stringify!($ident), "`] was not decoded successfully.")]
+    #[derive(Debug)]
+    $vis enum $error { $(
+        #[doc = concat!("The [`", stringify!($ty), "`] failed to decode.")]
+        $variant(<$ty as $crate::decode::PacketDecode>::Error)
+    , )+ }
+
+    impl ::core::fmt::Display for $error {
+        fn fmt(&self, f : &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result { match (self) { $(
+            Self::$variant(err) => write!(f, "{} {err}", stringify!($variant))
+        , )+ } }
+    }
+
+}
@@ -0,0 +1,149 @@
+//! Packet ID collision detection.
+
+
+use crate::meta::{
+    PacketMeta,
+    PacketState,
+    PacketBound
+};
+use core::fmt::{ self, Display, Formatter };
+use alloc::{
+    collections::btree_map::{ BTreeMap, Entry },
+    vec::Vec
+};
+
+
+/// The packet IDs of a protocol, keyed by state, direction, and prefix.
+///
+/// Prefixes are assigned by hand, so two packet types can easily be given the same one by mistake.
+/// A `PacketRegistry` is built with a [`PacketRegistryBuilder`], which reports every collision instead of letting the later packet replace the earlier one.
+#[derive(Clone, Debug)]
+pub struct PacketRegistry {
+    packets : BTreeMap<PacketKey, &'static str>
+}
+
+impl PacketRegistry {
+
+    /// Creates a new [`PacketRegistryBuilder`].
+    #[inline(always)]
+    pub fn builder() -> PacketRegistryBuilder { PacketRegistryBuilder::new() }
+
+    /// Returns the [`LABEL`](PacketMeta::LABEL) of the packet registered with this state, direction, and prefix.
+    #[inline]
+    pub fn label(&self, state : PacketState, bound : PacketBound, prefix : u32) -> Option<&'static str> {
+        self.packets.get(&PacketKey::new(state, bound, prefix)).copied()
+    }
+
+    /// Returns `true` if a packet is registered with this state, direction, and prefix.
+    #[inline]
+    pub fn contains(&self, state : PacketState, bound : PacketBound, prefix : u32) -> bool {
+        self.packets.contains_key(&PacketKey::new(state, bound, prefix))
+    }
+
+    /// Returns the number of registered packets.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.packets.len() }
+
+    /// Returns `true` if no packets are registered.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.packets.is_empty() }
+
+}
+
+
+/// Collects packet types into a [`PacketRegistry`].
+#[derive(Clone, Default, Debug)]
+pub struct PacketRegistryBuilder {
+    packets    : BTreeMap<PacketKey, &'static str>,
+    collisions : Vec<DuplicatePrefixError>
+}
+
+impl PacketRegistryBuilder {
+
+    /// Creates a new empty [`PacketRegistryBuilder`].
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+
+    /// Registers the packet type `P`.
+    ///
+    /// If another packet type was already registered with the same state, direction, and prefix, `P` is not registered,
+    ///  and the collision is returned by [`build`](Self::build).
+    #[inline]
+    pub fn register<P>(&mut self) -> &mut Self
+    where
+        P : PacketMeta + ?Sized
+    {
+        if let Err(err) = self.register_checked::<P>() {
+            self.collisions.push(err);
+        }
+        self
+    }
+
+    /// Registers the packet type `P`, returning an error if another packet type was already registered with the same state, direction, and prefix.
+    ///
+    /// On error, `P` is not registered, and the collision is not returned by [`build`](Self::build).
+    pub fn register_checked<P>(&mut self) -> Result<(), DuplicatePrefixError>
+    where
+        P : PacketMeta + ?Sized
+    { match (self.packets.entry(PacketKey::new(P::STATE, P::BOUND, P::PREFIX))) {
+        Entry::Vacant(entry) => {
+            entry.insert(P::LABEL);
+            Ok(())
+        },
+        Entry::Occupied(entry) => Err(DuplicatePrefixError {
+            state          : P::STATE,
+            bound          : P::BOUND,
+            prefix         : P::PREFIX,
+            existing_label : entry.get(),
+            label          : P::LABEL
+        })
+    } }
+
+    /// Finishes the registry, returning every collision found by [`register`](Self::register) if there were any.
+    pub fn build(self) -> Result<PacketRegistry, Vec<DuplicatePrefixError>> {
+        if (self.collisions.is_empty()) {
+            Ok(PacketRegistry { packets : self.packets })
+        } else {
+            Err(self.collisions)
+        }
+    }
+
+}
+
+
+/// The key of a packet in a [`PacketRegistry`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct PacketKey {
+    state  : u16,
+    c2s    : bool,
+    prefix : u32
+}
+
+impl PacketKey {
+    #[inline(always)]
+    const fn new(state : PacketState, bound : PacketBound, prefix : u32) -> Self {
+        Self { state : state.to_bits(), c2s : matches!(bound, PacketBound::C2S), prefix }
+    }
+}
+
+
+/// Returned by [`PacketRegistryBuilder`] when two packet types have the same state, direction, and prefix.
+#[derive(Clone, Debug)]
+pub struct DuplicatePrefixError {
+    /// The state of both packets.
+    pub state          : PacketState,
+    /// The direction of both packets.
+    pub bound          : PacketBound,
+    /// The prefix of both packets.
+    pub prefix         : u32,
+    /// The label of the packet which was already registered.
+    pub existing_label : &'static str,
+    /// The label of the packet which was not registered.
+    pub label          : &'static str
+}
+impl Display for DuplicatePrefixError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "packet {} has the same prefix {:#04x} as {} in {:?} {:?}", self.label, self.prefix, self.existing_label, self.bound, self.state)
+    }
+}
+impl core::error::Error for DuplicatePrefixError { }
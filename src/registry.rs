@@ -0,0 +1,178 @@
+//! Registry data entries, as sent by registry sync packets.
+
+
+use crate::{
+    decode::{
+        PacketDecode,
+        DecodeIter,
+        IncompleteDecodeError,
+        vec::VecDecodeError
+    },
+    encode::{
+        PacketEncode,
+        EncodeBuf
+    },
+    ident::{
+        Identifier,
+        IdentifierDecodeError
+    }
+};
+use core::{
+    fmt::{ self, Display, Formatter },
+    ops::Deref
+};
+use alloc::{
+    collections::BTreeSet,
+    vec::Vec
+};
+
+
+/// An entry in a registry, with optional data.
+///
+/// Encoded as an [`Identifier`], followed by a `bool` tag and the data if it is present.
+/// `D` is usually an NBT compound.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RegistryEntry<D> {
+    /// The identifier of the entry.
+    pub id   : Identifier,
+    /// The data of the entry, or `None` if the client should use the data from a known pack.
+    pub data : Option<D>
+}
+
+impl<D> PacketDecode for RegistryEntry<D>
+where
+    D : PacketDecode
+{
+    type Error = RegistryEntryDecodeError<D::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let id   = Identifier::decode(iter).map_err(RegistryEntryDecodeError::Id)?;
+        let data = if (bool::decode(iter).map_err(RegistryEntryDecodeError::Tag)?) {
+            Some(D::decode(iter).map_err(RegistryEntryDecodeError::Data)?)
+        } else { None };
+        Ok(Self { id, data })
+    }
+}
+
+unsafe impl<D> PacketEncode for RegistryEntry<D>
+where
+    D : PacketEncode
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.id.encode_len() + self.data.encode_len()
+    }
+
+    #[inline]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        self.id.encode(buf);
+        self.data.encode(buf);
+    } }
+
+}
+
+
+/// The entries of a registry, prefixed with a [`VarInt<u32>`](crate::varint::VarInt) count.
+///
+/// Decoding fails if two entries have the same identifier.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct RegistryEntries<D>(pub Vec<RegistryEntry<D>>);
+
+impl<D> From<Vec<RegistryEntry<D>>> for RegistryEntries<D> {
+    #[inline(always)]
+    fn from(value : Vec<RegistryEntry<D>>) -> Self { Self(value) }
+}
+
+impl<D> Deref for RegistryEntries<D> {
+    type Target = [RegistryEntry<D>];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<D> PacketDecode for RegistryEntries<D>
+where
+    D : PacketDecode
+{
+    type Error = RegistryEntriesDecodeError<D::Error>;
+
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let entries = Vec::<RegistryEntry<D>>::decode(iter).map_err(RegistryEntriesDecodeError::Entries)?;
+        let mut ids = BTreeSet::new();
+        for entry in &entries {
+            if (! ids.insert(&entry.id)) {
+                return Err(RegistryEntriesDecodeError::Duplicate(entry.id.clone()));
+            }
+        }
+        Ok(Self(entries))
+    }
+}
+
+unsafe impl<D> PacketEncode for RegistryEntries<D>
+where
+    D : PacketEncode
+{
+
+    #[inline(always)]
+    fn encode_len(&self) -> usize { <[RegistryEntry<D>]>::encode_len(&self.0) }
+
+    #[inline(always)]
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        <[RegistryEntry<D>]>::encode(&self.0, buf)
+    } }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn encode_to_writer<W>(&self, writer : &mut W) -> std::io::Result<()>
+    where
+        W : std::io::Write + ?Sized
+    { <[RegistryEntry<D>]>::encode_to_writer(&self.0, writer) }
+
+}
+
+
+/// Returned by packet decoders when a [`RegistryEntry`] was not decoded successfully.
+#[derive(Debug)]
+pub enum RegistryEntryDecodeError<E> {
+    /// The identifier failed to decode.
+    Id(IdentifierDecodeError),
+    /// The tag of the data failed to decode.
+    Tag(IncompleteDecodeError),
+    /// The data failed to decode.
+    Data(E)
+}
+impl<E> Display for RegistryEntryDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Id(err)   => write!(f, "id {err}"),
+        Self::Tag(err)  => write!(f, "tag {err}"),
+        Self::Data(err) => write!(f, "data {err}")
+    } }
+}
+
+
+/// Returned by packet decoders when [`RegistryEntries`] were not decoded successfully.
+#[derive(Debug)]
+pub enum RegistryEntriesDecodeError<E> {
+    /// The entries failed to decode.
+    Entries(VecDecodeError<RegistryEntryDecodeError<E>>),
+    /// Two entries had the same identifier.
+    Duplicate(Identifier)
+}
+impl<E> Display for RegistryEntriesDecodeError<E>
+where
+    E : Display
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Entries(err)  => err.fmt(f),
+        Self::Duplicate(id) => write!(f, "duplicate entry {id}")
+    } }
+}
@@ -5,7 +5,9 @@ use crate::{
     decode::{
         PacketDecode,
         DecodeIter,
-        IncompleteDecodeError
+        IncompleteDecodeError,
+        decode_prefixed_len,
+        vec::VecDecodeError
     },
     encode::{
         PacketEncode,
@@ -14,8 +16,11 @@ use crate::{
 };
 use core::{
     fmt::{ self, Display, Formatter },
-    ops::Deref
+    marker::PhantomData,
+    ops::{ Deref, DerefMut },
+    str::FromStr
 };
+use alloc::vec::Vec;
 
 
 /// A variable-length integer.
@@ -37,40 +42,97 @@ where
     fn deref(&self) -> &Self::Target { &self.0 }
 }
 
+impl<T> Display for VarInt<T>
+where
+    T : VarIntType + Display
+{
+    #[inline(always)]
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
+}
+
+impl<T> FromStr for VarInt<T>
+where
+    T : VarIntType + FromStr
+{
+    type Err = T::Err;
+
+    #[inline]
+    fn from_str(s : &str) -> Result<Self, Self::Err> { T::from_str(s).map(Self) }
+}
+
 
 const SEGMENT_BITS : u8 = 0b01111111;
 const CONTINUE_BIT : u8 = 0b10000000;
 
 
-/// An inner type for VarInts.
+/// An inner type for VarInts: one of `i32`, `u32`, `i64`, or `u64`.
+///
+/// This trait is sealed, and can only be used as a bound in generic code.
+///
+/// ```
+/// use pipeworkmc_codec::{
+///     decode::{ PacketDecode, DecodeIter },
+///     varint::{ VarInt, VarIntType, VarIntDecodeError }
+/// };
+///
+/// fn read_two<T, I>(iter : &mut DecodeIter<I>) -> Result<(T, T,), VarIntDecodeError>
+/// where
+///     T : VarIntType,
+///     I : ExactSizeIterator<Item = u8>
+/// { Ok((*VarInt::<T>::decode(iter)?, *VarInt::<T>::decode(iter)?,)) }
 ///
-/// ### Safety
-/// This trait requires the same guarantees as [`PacketEncode`].
-pub unsafe trait VarIntType
+/// let mut iter = DecodeIter::from([0x01, 0xFF, 0x01].into_iter());
+/// assert_eq!(read_two::<u32, _>(&mut iter).unwrap(), (1, 255,));
+/// ```
+pub trait VarIntType
 where
-    Self : Copy + Sized
-{
+    Self : sealed::VarIntTypeImpl
+{ }
+
+impl VarIntType for i32 { }
+impl VarIntType for u32 { }
+impl VarIntType for i64 { }
+impl VarIntType for u64 { }
+
+
+mod sealed {
+    use super::VarIntDecodeError;
 
-    /// Analagous to [`PacketDecode::decode`].
+    /// The en/decoding implementation of a [`VarIntType`](super::VarIntType).
     ///
-    /// Must return the number of bytes that were consumed.
-    fn decode(iter : impl Iterator<Item = u8>)
-        -> Result<(Self, usize,), VarIntDecodeError>;
+    /// ### Safety
+    /// This trait requires the same guarantees as [`PacketEncode`](crate::encode::PacketEncode).
+    pub unsafe trait VarIntTypeImpl
+    where
+        Self : Copy + Sized
+    {
 
-    /// The buffer type that the encoding process will use.
-    type EncodeBuf : Default;
+        /// Analagous to [`PacketDecode::decode`](crate::decode::PacketDecode::decode).
+        ///
+        /// Must return the number of bytes that were consumed.
+        fn decode(iter : impl Iterator<Item = u8>)
+            -> Result<(Self, usize,), VarIntDecodeError>;
 
-    /// Analagous to [`PacketEncode::encode_len`].
-    fn encode_len(self) -> usize;
+        /// The buffer type that the encoding process will use.
+        type EncodeBuf : Default;
 
-    /// Analagous to [`PacketEncode::encode`].
-    unsafe fn encode(self, buf : &mut Self::EncodeBuf) -> &[u8];
+        /// Analagous to [`PacketEncode::encode_len`](crate::encode::PacketEncode::encode_len).
+        fn encode_len(self) -> usize;
+
+        /// Analagous to [`PacketEncode::encode`](crate::encode::PacketEncode::encode).
+        ///
+        /// ### Safety
+        /// The implementor is responsible for ensuring that `Self::EncodeBuf` has enough space to hold any value of `Self`.
+        unsafe fn encode(self, buf : &mut Self::EncodeBuf) -> &[u8];
+
+    }
 
 }
+use sealed::VarIntTypeImpl;
 
 
 macro impl_varinttype_for_signed_int($unsigned_ty:ty => $signed_ty:ty) {
-    unsafe impl VarIntType for $signed_ty {
+    unsafe impl VarIntTypeImpl for $signed_ty {
 
         fn decode(mut iter : impl Iterator<Item = u8>)
             -> Result<(Self, usize,), VarIntDecodeError>
@@ -80,20 +142,26 @@ macro impl_varinttype_for_signed_int($unsigned_ty:ty => $signed_ty:ty) {
             let mut shift    = 0;
             let mut consumed = 0;
             loop {
-                let byte = iter.next().ok_or(IncompleteDecodeError)?;
+                let byte = iter.next().ok_or(IncompleteDecodeError::unknown())?;
                 consumed += 1;
+                let unused_bits = MAX_SHIFT - shift;
+                if (unused_bits < 7) {
+                    // This is the last byte which `Self` has room for. It must not continue,
+                    //  and must not carry any bits beyond the width of `Self`.
+                    if ((byte & CONTINUE_BIT) != 0) { return Err(VarIntDecodeError::TooLong); }
+                    if (((byte & SEGMENT_BITS) >> unused_bits) != 0) { return Err(VarIntDecodeError::Overflow); }
+                }
                 value |= ((byte & SEGMENT_BITS) as $signed_ty) << shift;
                 if ((byte & CONTINUE_BIT) == 0) { break; }
                 shift += 7;
-                if (shift > MAX_SHIFT) { return Err(VarIntDecodeError::TooLong); }
             }
             Ok((value, consumed,))
         }
 
-        type EncodeBuf = [u8; size_of::<Self>() + 1];
+        type EncodeBuf = [u8; (<$signed_ty>::BITS as usize).div_ceil(7)];
 
         fn encode_len(self) -> usize {
-            <$unsigned_ty as VarIntType>::encode_len(self.cast_unsigned())
+            <$unsigned_ty as VarIntTypeImpl>::encode_len(self.cast_unsigned())
         }
 
         unsafe fn encode(mut self, buf : &mut Self::EncodeBuf) -> &[u8] {
@@ -117,18 +185,18 @@ macro impl_varinttype_for_signed_int($unsigned_ty:ty => $signed_ty:ty) {
 }
 
 macro impl_varinttype_for_unsigned_int($signed_ty:ty => $unsigned_ty:ty) {
-    unsafe impl VarIntType for $unsigned_ty {
+    unsafe impl VarIntTypeImpl for $unsigned_ty {
 
         #[inline]
         fn decode(iter : impl Iterator<Item = u8>)
             -> Result<(Self, usize,), VarIntDecodeError>
-        { <$signed_ty as VarIntType>::decode(iter).map(|(v, consumed,)|
+        { <$signed_ty as VarIntTypeImpl>::decode(iter).map(|(v, consumed,)|
             (v.cast_unsigned(), consumed,)
         ) }
 
         #[inline(always)]
         fn encode_len(self) -> usize {
-            for i in (1..(size_of::<Self>() + 1)).rev() {
+            for i in (1..(Self::BITS as usize).div_ceil(7)).rev() {
                 let mask = Self::MAX << (7 * i);
                 if ((self & mask) != 0) {
                     return i + 1;
@@ -137,11 +205,11 @@ macro impl_varinttype_for_unsigned_int($signed_ty:ty => $unsigned_ty:ty) {
             1
         }
 
-        type EncodeBuf = <$signed_ty as VarIntType>::EncodeBuf;
+        type EncodeBuf = <$signed_ty as VarIntTypeImpl>::EncodeBuf;
 
         #[inline]
         unsafe fn encode(self, buf : &mut Self::EncodeBuf) -> &[u8] { unsafe {
-            <$signed_ty as VarIntType>::encode(self.cast_signed(), buf)
+            <$signed_ty as VarIntTypeImpl>::encode(self.cast_signed(), buf)
         } }
 
     }
@@ -153,6 +221,47 @@ impl_varinttype_for_unsigned_int!(i32 => u32);
 impl_varinttype_for_unsigned_int!(i64 => u64);
 
 
+/// Decodes a `VarInt<u32>` from a byte iterator, without a [`DecodeIter`].
+///
+/// Returns the decoded value and the number of bytes that were consumed.
+#[inline]
+pub fn decode_u32(iter : impl Iterator<Item = u8>) -> Result<(u32, usize,), VarIntDecodeError> {
+    <u32 as VarIntTypeImpl>::decode(iter)
+}
+
+/// Decodes a `VarInt<u64>` from a byte iterator, without a [`DecodeIter`].
+///
+/// Returns the decoded value and the number of bytes that were consumed.
+#[inline]
+pub fn decode_u64(iter : impl Iterator<Item = u8>) -> Result<(u64, usize,), VarIntDecodeError> {
+    <u64 as VarIntTypeImpl>::decode(iter)
+}
+
+
+impl<I> DecodeIter<I>
+where
+    I : ExactSizeIterator<Item = u8>
+{
+
+    /// Reads a [`VarInt<u32>`](VarInt) and returns the inner value.
+    #[inline]
+    pub fn read_varint_u32(&mut self) -> Result<u32, VarIntDecodeError> { Ok(*VarInt::<u32>::decode(self)?) }
+
+    /// Reads a [`VarInt<i32>`](VarInt) and returns the inner value.
+    #[inline]
+    pub fn read_varint_i32(&mut self) -> Result<i32, VarIntDecodeError> { Ok(*VarInt::<i32>::decode(self)?) }
+
+    /// Reads a [`VarInt<u64>`](VarInt) and returns the inner value.
+    #[inline]
+    pub fn read_varint_u64(&mut self) -> Result<u64, VarIntDecodeError> { Ok(*VarInt::<u64>::decode(self)?) }
+
+    /// Reads a [`VarInt<i64>`](VarInt) and returns the inner value.
+    #[inline]
+    pub fn read_varint_i64(&mut self) -> Result<i64, VarIntDecodeError> { Ok(*VarInt::<i64>::decode(self)?) }
+
+}
+
+
 impl<T> PacketDecode for VarInt<T>
 where
     T : VarIntType
@@ -175,14 +284,172 @@ where
 
     #[inline(always)]
     fn encode_len(&self) -> usize {
-        <T as VarIntType>::encode_len(self.0)
+        <T as VarIntTypeImpl>::encode_len(self.0)
     }
 
     #[inline(always)]
     unsafe fn encode(&self, buf : &mut EncodeBuf) {
-        let mut bytes = <T as VarIntType>::EncodeBuf::default();
-         unsafe { buf.write_slice(<T as VarIntType>::encode(self.0, &mut bytes)); }
+        let mut bytes = <T as VarIntTypeImpl>::EncodeBuf::default();
+         unsafe { buf.write_slice(<T as VarIntTypeImpl>::encode(self.0, &mut bytes)); }
+    }
+
+}
+
+
+/// An iterator which lazily decodes a known number of `VarInt<T>`s from a [`DecodeIter`].
+///
+/// After an error is returned, the iterator will not yield any more items.
+pub struct VarIntIter<'l, I, T>
+where
+    I : ExactSizeIterator<Item = u8>,
+    T : VarIntType
+{
+    iter      : &'l mut DecodeIter<I>,
+    remaining : usize,
+    marker    : PhantomData<fn() -> T>
+}
+
+impl<'l, I, T> VarIntIter<'l, I, T>
+where
+    I : ExactSizeIterator<Item = u8>,
+    T : VarIntType
+{
+
+    /// Creates a new [`VarIntIter`] which will decode `count` values from `iter`.
+    #[inline(always)]
+    pub fn new(iter : &'l mut DecodeIter<I>, count : usize) -> Self {
+        Self { iter, remaining : count, marker : PhantomData }
+    }
+
+    /// Decodes a [`VarInt::<u32>`](VarInt) length from `iter`, and creates a new [`VarIntIter`] which will decode that many values.
+    #[inline]
+    pub fn len_prefixed(iter : &'l mut DecodeIter<I>) -> Result<Self, VarIntDecodeError> {
+        let count = *VarInt::<u32>::decode(iter)? as usize;
+        Ok(Self::new(iter, count))
+    }
+
+}
+
+impl<I, T> Iterator for VarIntIter<'_, I, T>
+where
+    I : ExactSizeIterator<Item = u8>,
+    T : VarIntType
+{
+    type Item = Result<T, VarIntDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if (self.remaining == 0) { return None; }
+        self.remaining -= 1;
+        match (VarInt::<T>::decode(self.iter)) {
+            Ok(VarInt(value)) => Some(Ok(value)),
+            Err(err)          => {
+                self.remaining = 0;
+                Some(Err(err))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining.min(1), Some(self.remaining),)
+    }
+}
+
+
+/// A [`VarInt::<u32>`](VarInt) length prefixed array of `VarInt<T>`s, such as a chunk section palette.
+///
+/// Encodes to the same bytes as `Vec<VarInt<T>>`, but stores the raw values and skips the per-item wrapper.
+/// [`VarIntArray::decode_into`] reuses an existing allocation, which is useful when decoding many palettes in a row.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct VarIntArray<T>(pub Vec<T>)
+where
+    T : VarIntType;
+
+impl<T> VarIntArray<T>
+where
+    T : VarIntType
+{
+
+    /// Clears this array, then decodes a new array into it, reusing its allocation.
+    ///
+    /// If an error is returned, this array will hold the values that were decoded before the error.
+    pub fn decode_into<I>(&mut self, iter : &mut DecodeIter<I>) -> Result<(), VecDecodeError<VarIntDecodeError>>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        self.0.clear();
+        // Every value takes up at least one byte, so the length can never exceed the number of remaining bytes.
+        let max    = iter.remaining();
+        let length = decode_prefixed_len(iter, max).map_err(VecDecodeError::Length)?;
+        self.0.reserve(length);
+        for i in 0..length {
+            let (value, _consumed,) = T::decode(&mut*iter).map_err(|err| VecDecodeError::Item { index : i, err })?;
+            self.0.push(value);
+        }
+        Ok(())
+    }
+
+}
+
+impl<T> From<Vec<T>> for VarIntArray<T>
+where
+    T : VarIntType
+{
+    #[inline(always)]
+    fn from(value : Vec<T>) -> Self { Self(value) }
+}
+
+impl<T> Deref for VarIntArray<T>
+where
+    T : VarIntType
+{
+    type Target = Vec<T>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> DerefMut for VarIntArray<T>
+where
+    T : VarIntType
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<T> PacketDecode for VarIntArray<T>
+where
+    T : VarIntType
+{
+    type Error = VecDecodeError<VarIntDecodeError>;
+
+    #[inline]
+    fn decode<I>(iter : &mut DecodeIter<I>) -> Result<Self, Self::Error>
+    where
+        I : ExactSizeIterator<Item = u8>
+    {
+        let mut array = Self(Vec::new());
+        array.decode_into(iter)?;
+        Ok(array)
     }
+}
+
+unsafe impl<T> PacketEncode for VarIntArray<T>
+where
+    T : VarIntType
+{
+
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.0.iter().fold(VarInt::<u32>(self.0.len() as u32).encode_len(), |len, value| len.saturating_add(<T as VarIntTypeImpl>::encode_len(*value)))
+    }
+
+    unsafe fn encode(&self, buf : &mut EncodeBuf) { unsafe {
+        VarInt::<u32>(self.0.len() as u32).encode(buf);
+        let mut bytes = <T as VarIntTypeImpl>::EncodeBuf::default();
+        for value in &self.0 {
+            buf.write_slice(<T as VarIntTypeImpl>::encode(*value, &mut bytes));
+        }
+    } }
 
 }
 
@@ -193,7 +460,9 @@ pub enum VarIntDecodeError {
     /// There were not enough bytes.
     Incomplete(IncompleteDecodeError),
     /// The decoded value was longer than the maximum number of bytes allowed by the protocol.
-    TooLong
+    TooLong,
+    /// The final byte set bits beyond the width of the target type.
+    Overflow
 }
 impl From<IncompleteDecodeError> for VarIntDecodeError {
     #[inline(always)]
@@ -202,6 +471,21 @@ impl From<IncompleteDecodeError> for VarIntDecodeError {
 impl Display for VarIntDecodeError {
     fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
         Self::Incomplete(err) => err.fmt(f),
-        Self::TooLong         => write!(f, "too long")
+        Self::TooLong         => write!(f, "too long"),
+        Self::Overflow        => write!(f, "value overflows target type")
     } }
 }
+impl core::error::Error for VarIntDecodeError { }
+/// Converts into an [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) error if there were not enough bytes,
+///  or an [`InvalidData`](std::io::ErrorKind::InvalidData) error otherwise.
+#[cfg(feature = "std")]
+impl From<VarIntDecodeError> for std::io::Error {
+    fn from(err : VarIntDecodeError) -> Self {
+        let kind = match (err) {
+            VarIntDecodeError::Incomplete(_) => std::io::ErrorKind::UnexpectedEof,
+            VarIntDecodeError::TooLong
+            | VarIntDecodeError::Overflow    => std::io::ErrorKind::InvalidData
+        };
+        Self::new(kind, err)
+    }
+}
@@ -0,0 +1,107 @@
+//! Enums encoded as a variable-length integer discriminant.
+
+
+use crate::varint::VarIntDecodeError;
+use core::fmt::{ self, Display, Formatter };
+
+
+/// An enum which is encoded as the [`VarInt<i32>`](crate::varint::VarInt) discriminant of its variant.
+///
+/// This is usually implemented through [`varint_enum`], which also implements
+///  [`PacketEncode`](crate::encode::PacketEncode) and [`PacketDecode`](crate::decode::PacketDecode).
+pub trait VarIntEnum
+where
+    Self : Sized
+{
+
+    /// Returns the discriminant of this variant.
+    fn discriminant(&self) -> i32;
+
+    /// Returns the variant with the given discriminant, if there is one.
+    fn from_discriminant(discriminant : i32) -> Option<Self>;
+
+}
+
+
+/// Defines a fieldless enum which is encoded as a [`VarInt<i32>`](crate::varint::VarInt) discriminant.
+///
+/// Variants may be given explicit discriminants, and gaps between discriminants are allowed.
+/// Decoding an unassigned discriminant returns [`EnumDecodeError::UnknownDiscriminant`].
+pub macro varint_enum(
+    $( #[$meta:meta] )*
+    $vis:vis enum $ident:ident {
+        $( $( #[$variant_meta:meta] )* $variant:ident $( = $discriminant:expr )? ),* $(,)?
+    }
+) {
+
+    $( #[$meta] )*
+    #[repr(i32)]
+    $vis enum $ident {
+        $( $( #[$variant_meta] )* $variant $( = $discriminant )? , )*
+    }
+
+    impl $crate::varint_enum::VarIntEnum for $ident {
+
+        #[inline]
+        fn discriminant(&self) -> i32 { match (self) {
+            $( Self::$variant => Self::$variant as i32 , )*
+        } }
+
+        #[allow(unreachable_code)]
+        fn from_discriminant(discriminant : i32) -> Option<Self> {
+            $( if (discriminant == (Self::$variant as i32)) { return Some(Self::$variant); } )*
+            None
+        }
+
+    }
+
+    impl $crate::decode::PacketDecode for $ident {
+        type Error = $crate::varint_enum::EnumDecodeError;
+
+        fn decode<I>(iter : &mut $crate::decode::DecodeIter<I>) -> Result<Self, Self::Error>
+        where
+            I : ExactSizeIterator<Item = u8>
+        {
+            let discriminant = *<$crate::varint::VarInt<i32> as $crate::decode::PacketDecode>::decode(iter)
+                .map_err($crate::varint_enum::EnumDecodeError::Discriminant)?;
+            <Self as $crate::varint_enum::VarIntEnum>::from_discriminant(discriminant)
+                .ok_or($crate::varint_enum::EnumDecodeError::UnknownDiscriminant(discriminant))
+        }
+    }
+
+    unsafe impl $crate::encode::PacketEncode for $ident {
+
+        #[inline]
+        fn encode_len(&self) -> usize {
+            $crate::encode::PacketEncode::encode_len(
+                &$crate::varint::VarInt::<i32>($crate::varint_enum::VarIntEnum::discriminant(self))
+            )
+        }
+
+        #[inline]
+        unsafe fn encode(&self, buf : &mut $crate::encode::EncodeBuf) { unsafe {
+            $crate::encode::PacketEncode::encode(
+                &$crate::varint::VarInt::<i32>($crate::varint_enum::VarIntEnum::discriminant(self)),
+                buf
+            )
+        } }
+
+    }
+
+}
+
+
+/// Returned by packet decoders when a [`VarIntEnum`] was not decoded successfully.
+#[derive(Debug)]
+pub enum EnumDecodeError {
+    /// The discriminant failed to decode.
+    Discriminant(VarIntDecodeError),
+    /// The discriminant does not match any variant.
+    UnknownDiscriminant(i32)
+}
+impl Display for EnumDecodeError {
+    fn fmt(&self, f : &mut Formatter<'_>) -> fmt::Result { match (self) {
+        Self::Discriminant(err)                 => write!(f, "discriminant {err}"),
+        Self::UnknownDiscriminant(discriminant) => write!(f, "unknown discriminant {discriminant}")
+    } }
+}
@@ -0,0 +1,79 @@
+//! Loom model of the `AtomicPacketState` atomics.
+//!
+//! Run with:
+//! ```sh
+//! RUSTFLAGS="--cfg loom" cargo test --test loom_state --release
+//! ```
+#![cfg(loom)]
+
+
+use pipeworkmc_codec::meta::{
+    AtomicPacketState,
+    PacketState
+};
+use core::sync::atomic::Ordering as AtomicOrdering;
+use loom::{
+    sync::Arc,
+    thread
+};
+
+
+/// Returns `true` if `state` is one of the states that the model can reach.
+fn is_reachable(state : PacketState) -> bool {
+    matches!(state, PacketState::Handshake | PacketState::Status | PacketState::Login | PacketState::Config | PacketState::Custom(7))
+}
+
+
+#[test]
+fn transition_races_fetch_update() {
+    loom::model(|| {
+        let state = Arc::new(AtomicPacketState::new(PacketState::Handshake));
+
+        let transitions = {
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                let logged_in = state.transition(PacketState::Handshake, PacketState::Login);
+                let previous  = state.compare_exchange(PacketState::Status, PacketState::Custom(7), AtomicOrdering::AcqRel, AtomicOrdering::Acquire);
+                let previous  = match (previous) { Ok(previous) | Err(previous) => previous };
+                assert!(is_reachable(previous), "observed invalid state {previous:?}");
+                logged_in
+            })
+        };
+
+        let previous = state.fetch_update(AtomicOrdering::AcqRel, AtomicOrdering::Acquire, |current| match (current) {
+            PacketState::Handshake => Some(PacketState::Status),
+            PacketState::Login     => Some(PacketState::Config),
+            _                      => None
+        });
+        let previous = match (previous) { Ok(previous) | Err(previous) => previous };
+        assert!(is_reachable(previous), "observed invalid state {previous:?}");
+
+        let logged_in = transitions.join().unwrap();
+        let state     = Arc::try_unwrap(state).unwrap().into_inner();
+        assert!(is_reachable(state), "ended in invalid state {state:?}");
+        // Exactly one of the two threads wins the race out of `Handshake`.
+        if (logged_in) {
+            assert!(matches!(state, PacketState::Login | PacketState::Config), "ended in {state:?} after logging in");
+        } else {
+            assert!(matches!(state, PacketState::Status | PacketState::Custom(7)), "ended in {state:?} without logging in");
+        }
+    });
+}
+
+
+#[test]
+fn swap_races_load() {
+    loom::model(|| {
+        let state = Arc::new(AtomicPacketState::new(PacketState::Config));
+
+        let swapper = {
+            let state = Arc::clone(&state);
+            thread::spawn(move || state.swap(PacketState::Custom(7), AtomicOrdering::AcqRel))
+        };
+
+        let loaded = state.load(AtomicOrdering::Acquire);
+        assert!(matches!(loaded, PacketState::Config | PacketState::Custom(7)), "loaded invalid state {loaded:?}");
+        assert_eq!(swapper.join().unwrap(), PacketState::Config);
+        assert_eq!(state.load(AtomicOrdering::Acquire), PacketState::Custom(7));
+    });
+}
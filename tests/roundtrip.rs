@@ -0,0 +1,83 @@
+//! Property tests checking that values survive an encode and decode unchanged,
+//!  and that `encode_len` matches the number of bytes written.
+
+
+use pipeworkmc_codec::{
+    decode::{ DecodeIter, PacketDecode },
+    encode::PacketEncode,
+    varint::VarInt
+};
+use core::fmt::Debug;
+use proptest::prelude::*;
+use uuid::Uuid;
+
+
+/// Encodes `value`, checks its length, and decodes it again as a `T`.
+fn roundtrip<E, T>(value : &E) -> Result<T, TestCaseError>
+where
+    E        : PacketEncode + ?Sized,
+    T        : PacketDecode,
+    T::Error : Debug
+{
+    let encoded = value.encode_to_vec();
+    prop_assert_eq!(value.encode_len(), encoded.len(), "`encode_len` does not match the encoded length");
+    let mut iter = DecodeIter::from(encoded.into_iter());
+    let decoded  = T::decode(&mut iter).map_err(|err| TestCaseError::fail(format!("failed to decode: {err:?}")))?;
+    prop_assert_eq!(iter.remaining(), 0, "decoding did not consume every byte");
+    Ok(decoded)
+}
+
+
+macro_rules! roundtrip_eq {
+    ( $( $name:ident : $ty:ty = $strategy:expr ; )* ) => { proptest! { $(
+        #[test]
+        fn $name(value in $strategy) {
+            let value : $ty = value;
+            prop_assert_eq!(roundtrip::<$ty, $ty>(&value)?, value);
+        }
+    )* } }
+}
+
+roundtrip_eq! {
+    roundtrip_u8         : u8          = any::<u8>();
+    roundtrip_i8         : i8          = any::<i8>();
+    roundtrip_u16        : u16         = any::<u16>();
+    roundtrip_i16        : i16         = any::<i16>();
+    roundtrip_u32        : u32         = any::<u32>();
+    roundtrip_i32        : i32         = any::<i32>();
+    roundtrip_u64        : u64         = any::<u64>();
+    roundtrip_i64        : i64         = any::<i64>();
+    roundtrip_u128       : u128        = any::<u128>();
+    roundtrip_i128       : i128        = any::<i128>();
+    roundtrip_bool       : bool        = any::<bool>();
+    roundtrip_uuid       : Uuid        = any::<u128>().prop_map(Uuid::from_u128);
+    roundtrip_string     : String      = ".{0,64}";
+    roundtrip_varint_i32 : VarInt<i32> = any::<i32>().prop_map(VarInt);
+    roundtrip_varint_i64 : VarInt<i64> = any::<i64>().prop_map(VarInt);
+    roundtrip_bytes      : Vec<u8>     = proptest::collection::vec(any::<u8>(), 0..512);
+}
+
+
+proptest! {
+
+    /// Arrays are encoded as slices, with a length.
+    #[test]
+    fn roundtrip_array(value in any::<[u8; 16]>()) {
+        prop_assert_eq!(roundtrip::<[u8], [u8; 16]>(&value)?, value);
+    }
+
+    /// Floats are compared by their bits, so that NaNs and signed zeros must also survive.
+    #[test]
+    fn roundtrip_f32(bits in any::<u32>()) {
+        let value = f32::from_bits(bits);
+        prop_assert_eq!(roundtrip::<f32, f32>(&value)?.to_bits(), bits);
+    }
+
+    /// Floats are compared by their bits, so that NaNs and signed zeros must also survive.
+    #[test]
+    fn roundtrip_f64(bits in any::<u64>()) {
+        let value = f64::from_bits(bits);
+        prop_assert_eq!(roundtrip::<f64, f64>(&value)?.to_bits(), bits);
+    }
+
+}